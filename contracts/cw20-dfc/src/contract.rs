@@ -1,20 +1,32 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult
+    to_binary, to_vec, Addr, Binary, CanonicalAddr, Deps, DepsMut, Env, MessageInfo, Order,
+    Response, StdError, StdResult, Storage, Uint128
 };
-use cw20_base::ContractError;
+use cw_storage_plus::{Bound, U64Key};
+use cw20::{AllowanceResponse, BalanceResponse};
 use cw20_base::enumerable::{query_all_allowances, query_all_accounts};
-use cw20_base::msg::{QueryMsg,ExecuteMsg};
+use cw20_base::state::{ALLOWANCES, BALANCES, TOKEN_INFO};
+use ripemd160::Ripemd160;
+use sha2::{Digest, Sha256};
 
-use crate::msg::MigrateMsg;
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, GetFeeExemptionsResponse, GetFeeInfoResponse, GetMintersResponse,
+    GetFrozenAddressesResponse, GetPermitNonceResponse, InstantiateMsg, MigrateMsg, MinterInfo, PermitPayload, QueryMsg, TotalSupplyResponse,
+};
+use crate::state::{
+    Config, MinterQuota, BALANCE_HISTORY, CONFIG, FEE_EXEMPT, FEE_TOTALS, FROZEN, MINTERS,
+    HISTORY_START, PERMIT_NONCES, TOTAL_SUPPLY_HISTORY,
+};
 use cw2::set_contract_version;
 use cw20_base::allowances::{
     execute_decrease_allowance, execute_increase_allowance, execute_send_from,
-    execute_transfer_from, query_allowance, execute_burn_from,
+    execute_transfer_from, query_allowance, execute_burn_from, deduct_allowance,
 };
 use cw20_base::contract::{
-    execute_mint, execute_send, execute_transfer, execute_update_marketing,
+    execute_send, execute_transfer, execute_update_marketing,
     execute_upload_logo, query_balance, query_token_info, query_minter, query_download_logo, query_marketing_info, execute_burn,
 };
 
@@ -22,19 +34,52 @@ use cw20_base::contract::{
 const CONTRACT_NAME: &str = "crates.io:cw20-dfc";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+const MAX_BPS: u64 = 100000;
+const MAX_FEE_RATE: u64 = 10000;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    msg: cw20_base::msg::InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    exempt_game_contracts(deps.branch(), &msg.dflunc_address, &msg.fomodfc_address)?;
+    let msg = cw20_base::msg::InstantiateMsg::from(msg);
+
+    // the cw20 minter becomes the first registered minter, the cw20 cap the global supply cap
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            admin: deps.api.addr_canonicalize(info.sender.as_str())?,
+            supply_cap: msg.get_cap(),
+            fee_rate: 0,
+            fee_recipient: None,
+        },
+    )?;
+    if let Some(mint) = &msg.mint {
+        let minter = deps.api.addr_validate(&mint.minter)?;
+        MINTERS.save(deps.storage, &minter, &MinterQuota { quota: None, minted: Uint128::zero() })?;
+    }
 
     /* Execute the instantiate method from cw_20_base as the code from that
     library is already battle tested we do not have to re-write the full
     functionality: https://github.com/CosmWasm/cw-plus/tree/main/contracts/cw20-base*/
-    Ok(cw20_base::contract::instantiate(deps, env, info, msg)?)
+    let initial_balances = msg.initial_balances.clone();
+    let res = cw20_base::contract::instantiate(deps.branch(), env.clone(), info, msg)?;
+    for row in initial_balances {
+        let address = deps.api.addr_validate(&row.address)?;
+        checkpoint_balances(deps.storage, &env, &[&address])?;
+    }
+    checkpoint_total_supply(deps.storage, &env)?;
+    HISTORY_START.save(deps.storage, &env.block.height)?;
+
+    Ok(res)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -43,46 +88,55 @@ pub fn execute(
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> Result<Response, cw20_base::ContractError> {
+) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::Transfer { recipient, amount } => {
-            execute_transfer(deps, env, info, recipient, amount)
-        }
-        ExecuteMsg::Burn { amount } => execute_burn(deps, env, info, amount),
+        ExecuteMsg::Transfer { recipient, amount } => transfer(deps, env, info, recipient, amount),
+        ExecuteMsg::Burn { amount } => burn(deps, env, info, amount),
         ExecuteMsg::Send {
             contract,
             amount,
             msg,
-        } => execute_send(deps, env, info, contract, amount, msg),
-        ExecuteMsg::Mint { recipient, amount } => execute_mint(deps, env, info, recipient.clone(), amount),
+        } => send(deps, env, info, contract, amount, msg),
+        ExecuteMsg::Mint { recipient, amount } => mint(deps, env, info, recipient, amount),
         ExecuteMsg::IncreaseAllowance {
             spender,
             amount,
             expires,
-        } => execute_increase_allowance(deps, env, info, spender, amount, expires),
+        } => Ok(execute_increase_allowance(deps, env, info, spender, amount, expires)?),
         ExecuteMsg::DecreaseAllowance {
             spender,
             amount,
             expires,
-        } => execute_decrease_allowance(deps, env, info, spender, amount, expires),
+        } => Ok(execute_decrease_allowance(deps, env, info, spender, amount, expires)?),
         ExecuteMsg::TransferFrom {
             owner,
             recipient,
             amount,
-        } => execute_transfer_from(deps, env, info, owner, recipient, amount),
-        ExecuteMsg::BurnFrom { owner, amount } => execute_burn_from(deps, env, info, owner, amount),
+        } => transfer_from(deps, env, info, owner, recipient, amount),
+        ExecuteMsg::BurnFrom { owner, amount } => burn_from(deps, env, info, owner, amount),
         ExecuteMsg::SendFrom {
             owner,
             contract,
             amount,
             msg,
-        } => execute_send_from(deps, env, info, owner, contract, amount, msg),
+        } => send_from(deps, env, info, owner, contract, amount, msg),
         ExecuteMsg::UpdateMarketing {
             project,
             description,
             marketing,
-        } => execute_update_marketing(deps, env, info, project, description, marketing),
-        ExecuteMsg::UploadLogo(logo) => execute_upload_logo(deps, env, info, logo),
+        } => Ok(execute_update_marketing(deps, env, info, project, description, marketing)?),
+        ExecuteMsg::UploadLogo(logo) => Ok(execute_upload_logo(deps, env, info, logo)?),
+        ExecuteMsg::AddMinter { minter, quota } => add_minter(deps, info, minter, quota),
+        ExecuteMsg::RemoveMinter { minter } => remove_minter(deps, info, minter),
+        ExecuteMsg::RenounceMinter {} => renounce_minter(deps, info),
+        ExecuteMsg::UpdateFeeConfig {
+            fee_rate,
+            fee_recipient,
+        } => update_fee_config(deps, info, fee_rate, fee_recipient),
+        ExecuteMsg::SetFeeExempt { address, exempt } => set_fee_exempt(deps, info, address, exempt),
+        ExecuteMsg::Permit { payload, signature } => permit(deps, env, payload, signature),
+        ExecuteMsg::Freeze { address } => freeze(deps, info, address),
+        ExecuteMsg::Unfreeze { address } => unfreeze(deps, info, address),
     }
 }
 
@@ -107,38 +161,689 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
         QueryMsg::MarketingInfo {} => to_binary(&query_marketing_info(deps)?),
         QueryMsg::DownloadLogo {} => to_binary(&query_download_logo(deps)?),
+        QueryMsg::GetMinters { start_after, limit } => {
+            to_binary(&query_minters(deps, start_after, limit)?)
+        }
+        QueryMsg::BalanceAt { address, height } => {
+            to_binary(&query_balance_at(deps, address, height)?)
+        }
+        QueryMsg::TotalSupplyAt { height } => to_binary(&query_total_supply_at(deps, height)?),
+        QueryMsg::GetFeeInfo {} => to_binary(&query_fee_info(deps)?),
+        QueryMsg::GetPermitNonce { owner } => to_binary(&query_permit_nonce(deps, owner)?),
+        QueryMsg::GetFeeExemptions { start_after, limit } => {
+            to_binary(&query_fee_exemptions(deps, start_after, limit)?)
+        }
+        QueryMsg::GetFrozenAddresses { start_after, limit } => {
+            to_binary(&query_frozen_addresses(deps, start_after, limit)?)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    exempt_game_contracts(deps.branch(), &msg.dflunc_address, &msg.fomodfc_address)?;
+
+    // contracts deployed before the minter registry keep their cw20 minter as minter,
+    // and as admin unless another one is given
+    if CONFIG.may_load(deps.storage)?.is_none() {
+        let token_info = TOKEN_INFO.load(deps.storage)?;
+        let admin = match (msg.admin, &token_info.mint) {
+            (Some(admin), _) => deps.api.addr_validate(&admin)?,
+            (None, Some(mint)) => mint.minter.clone(),
+            (None, None) => return Err(StdError::generic_err("An admin is required for a token without minter")),
+        };
+        CONFIG.save(
+            deps.storage,
+            &Config {
+                admin: deps.api.addr_canonicalize(admin.as_str())?,
+                supply_cap: token_info.mint.as_ref().and_then(|mint| mint.cap),
+                fee_rate: 0,
+                fee_recipient: None,
+            },
+        )?;
+        if let Some(mint) = token_info.mint {
+            MINTERS.save(deps.storage, &mint.minter, &MinterQuota { quota: None, minted: Uint128::zero() })?;
+        }
+    }
+
+    // balance history starts at the height the snapshots were introduced,
+    // accounts get their first checkpoint when their balance changes
+    if HISTORY_START.may_load(deps.storage)?.is_none() {
+        HISTORY_START.save(deps.storage, &env.block.height)?;
+        checkpoint_total_supply(deps.storage, &env)?;
+    }
     Ok(Response::default())
 }
 
-// fn mint(
-//     deps: DepsMut,
-//     env: Env,
-//     info: MessageInfo,
-//     recipient: String,
-//     amount: Uint128,
-// ) -> Result<Response, ContractError> {
-//     // authorize owner
-//     only_minter(deps.as_ref(), &info)?;
-
-//     execute_mint(deps, env, info, recipient.clone(), amount)?;
-
-//     let res = Response::new()
-//         .add_attribute("action", "mint")
-//         .add_attribute("to", recipient)
-//         .add_attribute("amount", amount);
-
-//     Ok(res)
-// }
-
-// fn only_minter(deps: Deps, info: &MessageInfo) -> Result<bool, ContractError> {
-//     let cfg = CONFIG.load(deps.storage)?;
-//     if info.sender != cfg.minter {
-//         return Err(ContractError::Unauthorized {});
-//     }
-//     Ok(true)
-// }
\ No newline at end of file
+// dflunc and fomodfc move DFC for players, charging them would tax every stake and burn
+fn exempt_game_contracts(
+    deps: DepsMut,
+    dflunc_address: &Option<String>,
+    fomodfc_address: &Option<String>,
+) -> StdResult<()> {
+    for address in [dflunc_address, fomodfc_address].iter().copied().flatten() {
+        let addr = deps.api.addr_validate(address)?;
+        FEE_EXEMPT.save(deps.storage, &addr, &true)?;
+    }
+    Ok(())
+}
+
+fn transfer(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    let sender = info.sender.clone();
+    ensure_not_frozen(deps.as_ref(), &[&sender])?;
+    seed_balance_history(deps.storage, &[&sender, &rcpt_addr])?;
+    let fee = transfer_fee(deps.as_ref(), &[&sender, &rcpt_addr], amount)?;
+
+    let res = execute_transfer(deps.branch(), env.clone(), info, recipient, amount - fee)?;
+    let res = collect_fee(deps.branch(), &env, &sender, fee, res)?;
+    checkpoint_balances(deps.storage, &env, &[&sender, &rcpt_addr])?;
+    Ok(res)
+}
+
+fn send(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let rcpt_addr = deps.api.addr_validate(&contract)?;
+    let sender = info.sender.clone();
+    ensure_not_frozen(deps.as_ref(), &[&sender])?;
+    seed_balance_history(deps.storage, &[&sender, &rcpt_addr])?;
+    let fee = transfer_fee(deps.as_ref(), &[&sender, &rcpt_addr], amount)?;
+
+    let res = execute_send(deps.branch(), env.clone(), info, contract, amount - fee, msg)?;
+    let res = collect_fee(deps.branch(), &env, &sender, fee, res)?;
+    checkpoint_balances(deps.storage, &env, &[&sender, &rcpt_addr])?;
+    Ok(res)
+}
+
+fn transfer_from(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    ensure_not_frozen(deps.as_ref(), &[&owner_addr, &info.sender])?;
+    seed_balance_history(deps.storage, &[&owner_addr, &rcpt_addr])?;
+    let fee = transfer_fee(deps.as_ref(), &[&owner_addr, &rcpt_addr, &info.sender], amount)?;
+
+    // the fee is paid by the owner and spent from the same allowance
+    if !fee.is_zero() {
+        deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, fee)?;
+    }
+    let res = execute_transfer_from(deps.branch(), env.clone(), info, owner, recipient, amount - fee)?;
+    let res = collect_fee(deps.branch(), &env, &owner_addr, fee, res)?;
+    checkpoint_balances(deps.storage, &env, &[&owner_addr, &rcpt_addr])?;
+    Ok(res)
+}
+
+fn send_from(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let rcpt_addr = deps.api.addr_validate(&contract)?;
+    ensure_not_frozen(deps.as_ref(), &[&owner_addr, &info.sender])?;
+    seed_balance_history(deps.storage, &[&owner_addr, &rcpt_addr])?;
+    let fee = transfer_fee(deps.as_ref(), &[&owner_addr, &rcpt_addr, &info.sender], amount)?;
+
+    if !fee.is_zero() {
+        deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, fee)?;
+    }
+    let res = execute_send_from(deps.branch(), env.clone(), info, owner, contract, amount - fee, msg)?;
+    let res = collect_fee(deps.branch(), &env, &owner_addr, fee, res)?;
+    checkpoint_balances(deps.storage, &env, &[&owner_addr, &rcpt_addr])?;
+    Ok(res)
+}
+
+fn burn(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let sender = info.sender.clone();
+    seed_balance_history(deps.storage, &[&sender])?;
+    let res = execute_burn(deps.branch(), env.clone(), info, amount)?;
+    checkpoint_balances(deps.storage, &env, &[&sender])?;
+    checkpoint_total_supply(deps.storage, &env)?;
+    Ok(res)
+}
+
+fn burn_from(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    seed_balance_history(deps.storage, &[&owner_addr])?;
+    let res = execute_burn_from(deps.branch(), env.clone(), info, owner, amount)?;
+    checkpoint_balances(deps.storage, &env, &[&owner_addr])?;
+    checkpoint_total_supply(deps.storage, &env)?;
+    Ok(res)
+}
+
+fn ensure_not_frozen(deps: Deps, addrs: &[&Addr]) -> Result<(), ContractError> {
+    for addr in addrs {
+        if FROZEN.may_load(deps.storage, addr)?.unwrap_or(false) {
+            return Err(ContractError::AddressFrozen { address: addr.to_string() });
+        }
+    }
+    Ok(())
+}
+
+fn transfer_fee(deps: Deps, parties: &[&Addr], amount: Uint128) -> StdResult<Uint128> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.fee_rate == 0 {
+        return Ok(Uint128::zero());
+    }
+    for party in parties {
+        if FEE_EXEMPT.may_load(deps.storage, party)?.unwrap_or(false) {
+            return Ok(Uint128::zero());
+        }
+    }
+    Ok(amount * Uint128::from(config.fee_rate) / Uint128::from(MAX_BPS))
+}
+
+fn collect_fee(
+    deps: DepsMut,
+    env: &Env,
+    payer: &Addr,
+    fee: Uint128,
+    res: Response,
+) -> Result<Response, ContractError> {
+    if fee.is_zero() {
+        return Ok(res);
+    }
+
+    BALANCES.update(
+        deps.storage,
+        payer,
+        |balance: Option<Uint128>| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_sub(fee)?)
+        },
+    )?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut fee_totals = FEE_TOTALS.may_load(deps.storage)?.unwrap_or_default();
+    let res = match config.fee_recipient {
+        Some(fee_recipient) => {
+            let fee_recipient = deps.api.addr_humanize(&fee_recipient)?;
+            seed_balance_history(deps.storage, &[&fee_recipient])?;
+            BALANCES.update(
+                deps.storage,
+                &fee_recipient,
+                |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + fee) },
+            )?;
+            fee_totals.redirected += fee;
+            checkpoint_balances(deps.storage, env, &[&fee_recipient])?;
+            res.add_attribute("fee_recipient", fee_recipient)
+        }
+        None => {
+            TOKEN_INFO.update(deps.storage, |mut token_info| -> StdResult<_> {
+                token_info.total_supply = token_info.total_supply.checked_sub(fee)?;
+                Ok(token_info)
+            })?;
+            fee_totals.burned += fee;
+            checkpoint_total_supply(deps.storage, env)?;
+            res.add_attribute("fee_burned", fee)
+        }
+    };
+    FEE_TOTALS.save(deps.storage, &fee_totals)?;
+
+    Ok(res.add_attribute("fee", fee))
+}
+
+fn mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(cw20_base::ContractError::InvalidZeroAmount {}.into());
+    }
+
+    let mut minter_quota = only_minter(deps.as_ref(), &info)?;
+    if let Some(remaining) = minter_quota.remaining() {
+        if amount > remaining {
+            return Err(ContractError::MinterQuotaExceeded {
+                minter: info.sender.to_string(),
+                remaining,
+                amount,
+            });
+        }
+    }
+    minter_quota.minted += amount;
+    MINTERS.save(deps.storage, &info.sender, &minter_quota)?;
+
+    // update supply and enforce the global cap
+    let config = CONFIG.load(deps.storage)?;
+    let mut token_info = TOKEN_INFO.load(deps.storage)?;
+    token_info.total_supply += amount;
+    if let Some(cap) = config.supply_cap {
+        if token_info.total_supply > cap {
+            return Err(ContractError::SupplyCapExceeded { cap, amount });
+        }
+    }
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    seed_balance_history(deps.storage, &[&rcpt_addr])?;
+    BALANCES.update(
+        deps.storage,
+        &rcpt_addr,
+        |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
+    )?;
+    checkpoint_balances(deps.storage, &env, &[&rcpt_addr])?;
+    checkpoint_total_supply(deps.storage, &env)?;
+
+    let res = Response::new()
+        .add_attribute("action", "mint")
+        .add_attribute("minter", info.sender)
+        .add_attribute("to", recipient)
+        .add_attribute("amount", amount);
+
+    Ok(res)
+}
+
+fn add_minter(
+    deps: DepsMut,
+    info: MessageInfo,
+    minter: String,
+    quota: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    only_admin(deps.as_ref(), &info)?;
+
+    let minter_addr = deps.api.addr_validate(&minter)?;
+    let minted = MINTERS.may_load(deps.storage, &minter_addr)?
+        .map(|minter_quota| minter_quota.minted)
+        .unwrap_or_default();
+    MINTERS.save(deps.storage, &minter_addr, &MinterQuota { quota, minted })?;
+
+    let res = Response::new()
+        .add_attribute("action", "add_minter")
+        .add_attribute("minter", minter)
+        .add_attribute("quota", quota.map(|q| q.to_string()).unwrap_or_else(|| String::from("unlimited")));
+
+    Ok(res)
+}
+
+fn remove_minter(
+    deps: DepsMut,
+    info: MessageInfo,
+    minter: String,
+) -> Result<Response, ContractError> {
+    only_admin(deps.as_ref(), &info)?;
+
+    let minter_addr = deps.api.addr_validate(&minter)?;
+    if !MINTERS.has(deps.storage, &minter_addr) {
+        return Err(ContractError::NotMinter { sender: minter });
+    }
+    MINTERS.remove(deps.storage, &minter_addr);
+
+    let res = Response::new()
+        .add_attribute("action", "remove_minter")
+        .add_attribute("minter", minter);
+
+    Ok(res)
+}
+
+fn renounce_minter(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    only_minter(deps.as_ref(), &info)?;
+    MINTERS.remove(deps.storage, &info.sender);
+
+    let res = Response::new()
+        .add_attribute("action", "renounce_minter")
+        .add_attribute("minter", info.sender);
+
+    Ok(res)
+}
+
+fn permit(
+    deps: DepsMut,
+    env: Env,
+    payload: PermitPayload,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    if payload.chain_id != env.block.chain_id || payload.contract != env.contract.address.as_str() {
+        return Err(ContractError::InvalidPermitDomain {
+            chain_id: payload.chain_id,
+            contract: payload.contract,
+        });
+    }
+    if payload.expires.is_expired(&env.block) {
+        return Err(ContractError::PermitExpired { expires: payload.expires.to_string() });
+    }
+    if payload.owner_pubkey.len() != 33 {
+        return Err(ContractError::InvalidPermitPubkey {});
+    }
+
+    let message_hash = Sha256::digest(&to_vec(&payload)?);
+    if !deps.api.secp256k1_verify(&message_hash, &signature, &payload.owner_pubkey).map_err(StdError::from)? {
+        return Err(ContractError::InvalidPermitSignature {});
+    }
+
+    // the owner is the account controlled by the signing key
+    let owner_hash = Ripemd160::digest(&Sha256::digest(&payload.owner_pubkey));
+    let owner = deps.api.addr_humanize(&CanonicalAddr::from(owner_hash.as_slice()))?;
+    let spender = deps.api.addr_validate(&payload.spender)?;
+    if spender == owner {
+        return Err(cw20_base::ContractError::CannotSetOwnAccount {}.into());
+    }
+
+    let expected = PERMIT_NONCES.may_load(deps.storage, &owner)?.unwrap_or_default();
+    if payload.nonce != expected {
+        return Err(ContractError::InvalidPermitNonce { expected, nonce: payload.nonce });
+    }
+    PERMIT_NONCES.save(deps.storage, &owner, &(expected + 1))?;
+
+    ALLOWANCES.save(
+        deps.storage,
+        (&owner, &spender),
+        &AllowanceResponse {
+            allowance: payload.amount,
+            expires: payload.expires,
+        },
+    )?;
+
+    let res = Response::new()
+        .add_attribute("action", "permit")
+        .add_attribute("owner", owner)
+        .add_attribute("spender", spender)
+        .add_attribute("amount", payload.amount)
+        .add_attribute("nonce", payload.nonce.to_string());
+
+    Ok(res)
+}
+
+fn checkpoint_balances(storage: &mut dyn Storage, env: &Env, addrs: &[&Addr]) -> StdResult<()> {
+    for addr in addrs {
+        let balance = BALANCES.may_load(storage, addr)?.unwrap_or_default();
+        BALANCE_HISTORY.save(storage, (addr, U64Key::from(env.block.height)), &balance)?;
+    }
+    Ok(())
+}
+
+// the first change of an account records the balance it held when the history started
+fn seed_balance_history(storage: &mut dyn Storage, addrs: &[&Addr]) -> StdResult<()> {
+    let history_start = match HISTORY_START.may_load(storage)? {
+        Some(history_start) => history_start,
+        None => return Ok(()),
+    };
+    for addr in addrs {
+        let has_history = BALANCE_HISTORY
+            .prefix(addr)
+            .keys(storage, None, None, Order::Ascending)
+            .next()
+            .is_some();
+        if !has_history {
+            let balance = BALANCES.may_load(storage, addr)?.unwrap_or_default();
+            BALANCE_HISTORY.save(storage, (addr, U64Key::from(history_start)), &balance)?;
+        }
+    }
+    Ok(())
+}
+
+fn ensure_history_started(storage: &dyn Storage, height: u64) -> StdResult<()> {
+    let history_start = HISTORY_START.may_load(storage)?.unwrap_or_default();
+    if height < history_start {
+        return Err(StdError::generic_err(format!(
+            "Balance history starts at height {}",
+            history_start
+        )));
+    }
+    Ok(())
+}
+
+fn checkpoint_total_supply(storage: &mut dyn Storage, env: &Env) -> StdResult<()> {
+    let token_info = TOKEN_INFO.load(storage)?;
+    TOTAL_SUPPLY_HISTORY.save(storage, U64Key::from(env.block.height), &token_info.total_supply)
+}
+
+fn update_fee_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_rate: u64,
+    fee_recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    only_admin(deps.as_ref(), &info)?;
+    if fee_rate > MAX_FEE_RATE {
+        return Err(ContractError::InvalidFee { fee_rate, max_fee_rate: MAX_FEE_RATE });
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.fee_rate = fee_rate;
+    config.fee_recipient = match &fee_recipient {
+        Some(fee_recipient) => Some(deps.api.addr_canonicalize(fee_recipient)?),
+        None => None,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    let res = Response::new()
+        .add_attribute("action", "update_fee_config")
+        .add_attribute("fee_rate", fee_rate.to_string())
+        .add_attribute("fee_recipient", fee_recipient.unwrap_or_else(|| String::from("burn")));
+
+    Ok(res)
+}
+
+fn set_fee_exempt(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    exempt: bool,
+) -> Result<Response, ContractError> {
+    only_admin(deps.as_ref(), &info)?;
+
+    let addr = deps.api.addr_validate(&address)?;
+    if exempt {
+        FEE_EXEMPT.save(deps.storage, &addr, &true)?;
+    } else {
+        FEE_EXEMPT.remove(deps.storage, &addr);
+    }
+
+    let res = Response::new()
+        .add_attribute("action", "set_fee_exempt")
+        .add_attribute("address", address)
+        .add_attribute("exempt", exempt.to_string());
+
+    Ok(res)
+}
+
+fn freeze(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    only_admin(deps.as_ref(), &info)?;
+
+    let addr = deps.api.addr_validate(&address)?;
+    FROZEN.save(deps.storage, &addr, &true)?;
+
+    let res = Response::new()
+        .add_attribute("action", "freeze")
+        .add_attribute("address", address);
+
+    Ok(res)
+}
+
+fn unfreeze(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    only_admin(deps.as_ref(), &info)?;
+
+    let addr = deps.api.addr_validate(&address)?;
+    FROZEN.remove(deps.storage, &addr);
+
+    let res = Response::new()
+        .add_attribute("action", "unfreeze")
+        .add_attribute("address", address);
+
+    Ok(res)
+}
+
+fn only_minter(deps: Deps, info: &MessageInfo) -> Result<MinterQuota, ContractError> {
+    MINTERS.may_load(deps.storage, &info.sender)?
+        .ok_or_else(|| ContractError::NotMinter { sender: info.sender.to_string() })
+}
+
+fn only_admin(deps: Deps, info: &MessageInfo) -> Result<bool, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.admin {
+        return Err(ContractError::NotAdmin {
+            sender: info.sender.to_string(),
+            admin: deps.api.addr_humanize(&config.admin)?.to_string(),
+        });
+    }
+    Ok(true)
+}
+
+fn query_minters(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<GetMintersResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let token_info = TOKEN_INFO.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let minters: StdResult<Vec<MinterInfo>> = MINTERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, v) = item?;
+            Ok(MinterInfo {
+                minter: String::from_utf8(k)?,
+                quota: v.quota,
+                minted: v.minted,
+                remaining_quota: v.remaining(),
+            })
+        })
+        .collect();
+
+    Ok(GetMintersResponse {
+        admin: deps.api.addr_humanize(&config.admin)?.to_string(),
+        supply_cap: config.supply_cap,
+        total_supply: token_info.total_supply,
+        minters: minters?,
+    })
+}
+
+fn query_fee_info(deps: Deps) -> StdResult<GetFeeInfoResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let fee_totals = FEE_TOTALS.may_load(deps.storage)?.unwrap_or_default();
+    let fee_recipient = match config.fee_recipient {
+        Some(fee_recipient) => Some(deps.api.addr_humanize(&fee_recipient)?.to_string()),
+        None => None,
+    };
+
+    Ok(GetFeeInfoResponse {
+        fee_rate: config.fee_rate,
+        fee_recipient,
+        total_burned: fee_totals.burned,
+        total_redirected: fee_totals.redirected,
+    })
+}
+
+fn query_fee_exemptions(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<GetFeeExemptionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let addresses: Result<Vec<_>, _> = FEE_EXEMPT
+        .keys(deps.storage, start, None, Order::Ascending)
+        .map(String::from_utf8)
+        .take(limit)
+        .collect();
+
+    Ok(GetFeeExemptionsResponse {
+        addresses: addresses?,
+    })
+}
+
+fn query_balance_at(deps: Deps, address: String, height: u64) -> StdResult<BalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    ensure_history_started(deps.storage, height)?;
+    // an account without checkpoints hasn't changed since the history started
+    let balance = match BALANCE_HISTORY
+        .prefix(&address)
+        .range(deps.storage, None, Some(Bound::inclusive(U64Key::from(height))), Order::Descending)
+        .next()
+        .transpose()?
+    {
+        Some((_, balance)) => balance,
+        None => BALANCES.may_load(deps.storage, &address)?.unwrap_or_default(),
+    };
+
+    Ok(BalanceResponse { balance })
+}
+
+fn query_total_supply_at(deps: Deps, height: u64) -> StdResult<TotalSupplyResponse> {
+    ensure_history_started(deps.storage, height)?;
+    let total_supply = TOTAL_SUPPLY_HISTORY
+        .range(deps.storage, None, Some(Bound::inclusive(U64Key::from(height))), Order::Descending)
+        .next()
+        .transpose()?
+        .map(|(_, total_supply)| total_supply)
+        .unwrap_or_default();
+
+    Ok(TotalSupplyResponse { total_supply })
+}
+
+fn query_permit_nonce(deps: Deps, owner: String) -> StdResult<GetPermitNonceResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let nonce = PERMIT_NONCES.may_load(deps.storage, &owner)?.unwrap_or_default();
+
+    Ok(GetPermitNonceResponse { nonce })
+}
+
+fn query_frozen_addresses(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<GetFrozenAddressesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let addresses: Result<Vec<_>, _> = FROZEN
+        .keys(deps.storage, start, None, Order::Ascending)
+        .map(String::from_utf8)
+        .take(limit)
+        .collect();
+
+    Ok(GetFrozenAddressesResponse {
+        addresses: addresses?,
+    })
+}
@@ -1,9 +1,46 @@
 use thiserror::Error;
+use cosmwasm_std::{StdError, Uint128};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Base(#[from] cw20_base::ContractError),
+
     #[error("NotMinter: Sender {sender} is not minter.")]
     NotMinter {
         sender: String,
     },
+
+    #[error("NotAdmin: Sender is {sender}, but admin is {admin}.")]
+    NotAdmin { sender: String, admin: String },
+
+    #[error("MinterQuotaExceeded: Minter {minter} can mint {remaining} more, requested {amount}.")]
+    MinterQuotaExceeded { minter: String, remaining: Uint128, amount: Uint128 },
+
+    #[error("SupplyCapExceeded: Supply cap is {cap}, minting {amount} would exceed it.")]
+    SupplyCapExceeded { cap: Uint128, amount: Uint128 },
+
+    #[error("InvalidFee: Fee rate is {fee_rate}, it should not be greater than {max_fee_rate}.")]
+    InvalidFee { fee_rate: u64, max_fee_rate: u64 },
+
+    #[error("InvalidPermitSignature: Signature does not match the permit payload.")]
+    InvalidPermitSignature {},
+
+    #[error("InvalidPermitPubkey: Owner pubkey should be a compressed secp256k1 key.")]
+    InvalidPermitPubkey {},
+
+    #[error("InvalidPermitDomain: Permit was signed for chain {chain_id} and contract {contract}.")]
+    InvalidPermitDomain { chain_id: String, contract: String },
+
+    #[error("InvalidPermitNonce: Expected nonce {expected}, got {nonce}.")]
+    InvalidPermitNonce { expected: u64, nonce: u64 },
+
+    #[error("PermitExpired: Permit expired at {expires}.")]
+    PermitExpired { expires: String },
+
+    #[error("AddressFrozen: {address} is frozen.")]
+    AddressFrozen { address: String },
 }
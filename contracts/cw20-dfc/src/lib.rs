@@ -1,5 +1,9 @@
 pub mod contract;
 pub mod msg;
 mod error;
+pub mod state;
 
-pub use crate::error::ContractError;
\ No newline at end of file
+#[cfg(test)]
+mod testing;
+
+pub use crate::error::ContractError;
@@ -1,5 +1,239 @@
+use cosmwasm_std::{Binary, Uint128};
+use cw20::{Cw20Coin, Expiration, Logo, MinterResponse};
+use cw20_base::msg::InstantiateMarketingInfo;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
\ No newline at end of file
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub initial_balances: Vec<Cw20Coin>,
+    pub mint: Option<MinterResponse>,
+    pub marketing: Option<InstantiateMarketingInfo>,
+    /// Game contracts moving DFC on behalf of players, exempt from the transfer fee.
+    pub dflunc_address: Option<String>,
+    pub fomodfc_address: Option<String>,
+}
+
+impl From<InstantiateMsg> for cw20_base::msg::InstantiateMsg {
+    fn from(msg: InstantiateMsg) -> Self {
+        cw20_base::msg::InstantiateMsg {
+            name: msg.name,
+            symbol: msg.symbol,
+            decimals: msg.decimals,
+            initial_balances: msg.initial_balances,
+            mint: msg.mint,
+            marketing: msg.marketing,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+    },
+    Burn {
+        amount: Uint128,
+    },
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    SendFrom {
+        owner: String,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    BurnFrom {
+        owner: String,
+        amount: Uint128,
+    },
+    Mint {
+        recipient: String,
+        amount: Uint128,
+    },
+    UpdateMarketing {
+        project: Option<String>,
+        description: Option<String>,
+        marketing: Option<String>,
+    },
+    UploadLogo(Logo),
+    /// Admin only. Registers a new minter, or replaces the quota of an existing one.
+    /// A `None` quota leaves the minter bounded by the global supply cap only.
+    AddMinter {
+        minter: String,
+        quota: Option<Uint128>,
+    },
+    /// Admin only.
+    RemoveMinter {
+        minter: String,
+    },
+    /// Called by a minter to give up its own minting right.
+    RenounceMinter {},
+    /// Admin only. Sets the transfer fee charged on transfers and sends.
+    /// `fee_rate` is in 1/100000 of the amount (1000 is 1%) and at most 10000.
+    /// Without a fee recipient the fee is burned.
+    UpdateFeeConfig {
+        fee_rate: u64,
+        fee_recipient: Option<String>,
+    },
+    /// Admin only. Transfers touching an exempt address are not charged.
+    SetFeeExempt {
+        address: String,
+        exempt: bool,
+    },
+    /// Sets the spender's allowance on behalf of the owner derived from `payload.owner_pubkey`.
+    /// `signature` is a secp256k1 signature over the sha256 hash of the JSON encoded payload.
+    Permit {
+        payload: PermitPayload,
+        signature: Binary,
+    },
+    /// Admin only. A frozen address can't transfer or send, and allowances it granted
+    /// can't be spent. Mints to it and burns by it still work.
+    Freeze {
+        address: String,
+    },
+    /// Admin only.
+    Unfreeze {
+        address: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PermitPayload {
+    pub chain_id: String,
+    pub contract: String,
+    // compressed secp256k1 public key of the owner
+    pub owner_pubkey: Binary,
+    pub spender: String,
+    pub amount: Uint128,
+    // the permit can't be used once expired, the allowance expires with it
+    pub expires: Expiration,
+    // must equal the owner's current permit nonce
+    pub nonce: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Balance {
+        address: String,
+    },
+    /// Balance at the end of the given block height, heights before the history starts are rejected.
+    BalanceAt {
+        address: String,
+        height: u64,
+    },
+    TokenInfo {},
+    /// Total supply at the end of the given block height, heights before the history starts are rejected.
+    TotalSupplyAt {
+        height: u64,
+    },
+    Minter {},
+    Allowance {
+        owner: String,
+        spender: String,
+    },
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    MarketingInfo {},
+    DownloadLogo {},
+    GetMinters {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    GetFeeInfo {},
+    GetPermitNonce {
+        owner: String,
+    },
+    GetFeeExemptions {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    GetFrozenAddresses {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MinterInfo {
+    pub minter: String,
+    pub quota: Option<Uint128>,
+    pub minted: Uint128,
+    pub remaining_quota: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetMintersResponse {
+    pub admin: String,
+    pub supply_cap: Option<Uint128>,
+    pub total_supply: Uint128,
+    pub minters: Vec<MinterInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TotalSupplyResponse {
+    pub total_supply: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetFeeInfoResponse {
+    pub fee_rate: u64,
+    pub fee_recipient: Option<String>,
+    pub total_burned: Uint128,
+    pub total_redirected: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetFeeExemptionsResponse {
+    pub addresses: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetFrozenAddressesResponse {
+    pub addresses: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetPermitNonceResponse {
+    pub nonce: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+    /// Admin for tokens deployed before the config existed, defaults to the cw20 minter.
+    pub admin: Option<String>,
+    /// Exempted from the transfer fee when given.
+    pub dflunc_address: Option<String>,
+    pub fomodfc_address: Option<String>,
+}
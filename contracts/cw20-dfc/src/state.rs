@@ -0,0 +1,47 @@
+use cosmwasm_std::{Addr, CanonicalAddr, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub admin: CanonicalAddr,
+    // hard cap on total supply shared by every minter
+    pub supply_cap: Option<Uint128>,
+    // transfer fee in 1/100000 of the amount (1000 is 1%), zero disables it
+    pub fee_rate: u64,
+    // None burns the fee, otherwise it is redirected to this address
+    pub fee_recipient: Option<CanonicalAddr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MinterQuota {
+    // None means the minter is only bounded by the supply cap
+    pub quota: Option<Uint128>,
+    pub minted: Uint128,
+}
+
+impl MinterQuota {
+    pub fn remaining(&self) -> Option<Uint128> {
+        self.quota.map(|quota| quota.saturating_sub(self.minted))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct FeeTotals {
+    pub burned: Uint128,
+    pub redirected: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("CONFIG");
+pub const MINTERS: Map<&Addr, MinterQuota> = Map::new("MINTERS");
+pub const FEE_EXEMPT: Map<&Addr, bool> = Map::new("FEE_EXEMPT");
+pub const FEE_TOTALS: Item<FeeTotals> = Item::new("FEE_TOTALS");
+pub const PERMIT_NONCES: Map<&Addr, u64> = Map::new("PERMIT_NONCES");
+pub const FROZEN: Map<&Addr, bool> = Map::new("FROZEN");
+
+// balances and total supply as of the end of every block in which they changed
+pub const BALANCE_HISTORY: Map<(&Addr, U64Key), Uint128> = Map::new("BALANCE_HISTORY");
+pub const TOTAL_SUPPLY_HISTORY: Map<U64Key, Uint128> = Map::new("TOTAL_SUPPLY_HISTORY");
+// height the history starts at, accounts without a checkpoint still hold the balance they had then
+pub const HISTORY_START: Item<u64> = Item::new("HISTORY_START");
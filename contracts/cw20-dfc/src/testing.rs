@@ -0,0 +1,545 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    from_binary, to_vec, Addr, Api, Binary, CanonicalAddr, Deps, DepsMut, Env, OwnedDeps, RecoverPubkeyError,
+    StdError, StdResult, Uint128, VerificationError,
+};
+use cw20::{AllowanceResponse, BalanceResponse, Cw20Coin, Expiration, MinterResponse, TokenInfoResponse};
+use k256::ecdsa::signature::Signer;
+use k256::ecdsa::{Signature, SigningKey};
+use ripemd160::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::contract::{execute, instantiate, migrate, query};
+use crate::msg::{
+    ExecuteMsg, GetFeeExemptionsResponse, GetFeeInfoResponse, GetFrozenAddressesResponse, GetMintersResponse, InstantiateMsg, MigrateMsg,
+    GetPermitNonceResponse, MinterInfo, PermitPayload, QueryMsg, TotalSupplyResponse,
+};
+use crate::ContractError;
+
+fn instantiate_token(deps: DepsMut, dflunc_address: Option<&str>, fomodfc_address: Option<&str>) {
+    let msg = InstantiateMsg {
+        name: "DFC Token".to_string(),
+        symbol: "DFC".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin { address: "alice".to_string(), amount: Uint128::from(100000u128) }],
+        mint: Some(MinterResponse { minter: "minter".to_string(), cap: None }),
+        marketing: None,
+        dflunc_address: dflunc_address.map(String::from),
+        fomodfc_address: fomodfc_address.map(String::from),
+    };
+    instantiate(deps, mock_env(), mock_info("admin", &[]), msg).unwrap();
+}
+
+fn set_fee(deps: DepsMut, fee_rate: u64, fee_recipient: Option<&str>) {
+    let msg = ExecuteMsg::UpdateFeeConfig { fee_rate, fee_recipient: fee_recipient.map(String::from) };
+    execute(deps, mock_env(), mock_info("admin", &[]), msg).unwrap();
+}
+
+fn approve(deps: DepsMut, owner: &str, spender: &str, amount: u128) {
+    let msg = ExecuteMsg::IncreaseAllowance { spender: spender.to_string(), amount: Uint128::from(amount), expires: None };
+    execute(deps, mock_env(), mock_info(owner, &[]), msg).unwrap();
+}
+
+fn balance(deps: Deps, address: &str) -> Uint128 {
+    let msg = QueryMsg::Balance { address: address.to_string() };
+    let res: BalanceResponse = from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+    res.balance
+}
+
+fn total_supply(deps: Deps) -> Uint128 {
+    let res: TokenInfoResponse = from_binary(&query(deps, mock_env(), QueryMsg::TokenInfo {}).unwrap()).unwrap();
+    res.total_supply
+}
+
+fn fee_info(deps: Deps) -> GetFeeInfoResponse {
+    from_binary(&query(deps, mock_env(), QueryMsg::GetFeeInfo {}).unwrap()).unwrap()
+}
+
+#[test]
+fn transfer_fee_is_burned_without_recipient() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_token(deps.as_mut(), None, None);
+    set_fee(deps.as_mut(), 1000, None);
+
+    let msg = ExecuteMsg::Transfer { recipient: "bob".to_string(), amount: Uint128::from(10000u128) };
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+
+    assert_eq!(balance(deps.as_ref(), "alice"), Uint128::from(90000u128));
+    assert_eq!(balance(deps.as_ref(), "bob"), Uint128::from(9900u128));
+    assert_eq!(total_supply(deps.as_ref()), Uint128::from(99900u128));
+    assert_eq!(fee_info(deps.as_ref()).total_burned, Uint128::from(100u128));
+}
+
+#[test]
+fn send_fee_is_redirected_to_recipient() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_token(deps.as_mut(), None, None);
+    set_fee(deps.as_mut(), 1000, Some("treasury"));
+
+    let msg = ExecuteMsg::Send { contract: "vault".to_string(), amount: Uint128::from(10000u128), msg: Binary::default() };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    assert_eq!(balance(deps.as_ref(), "alice"), Uint128::from(90000u128));
+    assert_eq!(balance(deps.as_ref(), "vault"), Uint128::from(9900u128));
+    assert_eq!(balance(deps.as_ref(), "treasury"), Uint128::from(100u128));
+    assert_eq!(total_supply(deps.as_ref()), Uint128::from(100000u128));
+    assert_eq!(fee_info(deps.as_ref()).total_redirected, Uint128::from(100u128));
+}
+
+#[test]
+fn transfer_from_and_send_from_spend_the_fee_from_the_allowance() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_token(deps.as_mut(), None, None);
+    set_fee(deps.as_mut(), 1000, None);
+    approve(deps.as_mut(), "alice", "spender", 20000);
+
+    let msg = ExecuteMsg::TransferFrom {
+        owner: "alice".to_string(),
+        recipient: "bob".to_string(),
+        amount: Uint128::from(10000u128),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap();
+    let msg = ExecuteMsg::SendFrom {
+        owner: "alice".to_string(),
+        contract: "vault".to_string(),
+        amount: Uint128::from(10000u128),
+        msg: Binary::default(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap();
+
+    assert_eq!(balance(deps.as_ref(), "alice"), Uint128::from(80000u128));
+    assert_eq!(balance(deps.as_ref(), "bob"), Uint128::from(9900u128));
+    assert_eq!(balance(deps.as_ref(), "vault"), Uint128::from(9900u128));
+    assert_eq!(fee_info(deps.as_ref()).total_burned, Uint128::from(200u128));
+
+    // the allowance is used up by the amounts, fees included
+    let msg = QueryMsg::Allowance { owner: "alice".to_string(), spender: "spender".to_string() };
+    let res: AllowanceResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.allowance, Uint128::zero());
+}
+
+#[test]
+fn exempt_addresses_are_not_charged() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_token(deps.as_mut(), None, None);
+    set_fee(deps.as_mut(), 1000, None);
+    let msg = ExecuteMsg::SetFeeExempt { address: "bob".to_string(), exempt: true };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Transfer { recipient: "bob".to_string(), amount: Uint128::from(10000u128) };
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+    assert_eq!(balance(deps.as_ref(), "bob"), Uint128::from(10000u128));
+
+    let msg = ExecuteMsg::SetFeeExempt { address: "bob".to_string(), exempt: false };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    let msg = ExecuteMsg::Transfer { recipient: "bob".to_string(), amount: Uint128::from(10000u128) };
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+    assert_eq!(balance(deps.as_ref(), "bob"), Uint128::from(19900u128));
+}
+
+#[test]
+fn game_contracts_are_exempt_from_instantiate_and_migrate() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_token(deps.as_mut(), Some("dflunc"), None);
+    let msg = MigrateMsg { admin: None, dflunc_address: None, fomodfc_address: Some("fomodfc".to_string()) };
+    migrate(deps.as_mut(), mock_env(), msg).unwrap();
+
+    let msg = QueryMsg::GetFeeExemptions { start_after: None, limit: None };
+    let res: GetFeeExemptionsResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.addresses, vec!["dflunc".to_string(), "fomodfc".to_string()]);
+
+    set_fee(deps.as_mut(), 1000, None);
+    let msg = ExecuteMsg::Send { contract: "dflunc".to_string(), amount: Uint128::from(10000u128), msg: Binary::default() };
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+    assert_eq!(balance(deps.as_ref(), "dflunc"), Uint128::from(10000u128));
+}
+
+#[test]
+fn fee_rate_is_bounded() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_token(deps.as_mut(), None, None);
+
+    let msg = ExecuteMsg::UpdateFeeConfig { fee_rate: 10001, fee_recipient: None };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidFee { fee_rate: 10001, max_fee_rate: 10000 });
+}
+
+fn mint(deps: DepsMut, minter: &str, recipient: &str, amount: u128) -> Result<(), ContractError> {
+    let msg = ExecuteMsg::Mint { recipient: recipient.to_string(), amount: Uint128::from(amount) };
+    execute(deps, mock_env(), mock_info(minter, &[]), msg).map(|_| ())
+}
+
+fn minters(deps: Deps) -> GetMintersResponse {
+    let msg = QueryMsg::GetMinters { start_after: None, limit: None };
+    from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn minters_are_bounded_by_their_quota() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_token(deps.as_mut(), None, None);
+    let msg = ExecuteMsg::AddMinter { minter: "farm".to_string(), quota: Some(Uint128::from(1000u128)) };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::NotAdmin { sender: "alice".to_string(), admin: "admin".to_string() });
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    mint(deps.as_mut(), "farm", "bob", 600).unwrap();
+    let err = mint(deps.as_mut(), "farm", "bob", 500).unwrap_err();
+    assert_eq!(err, ContractError::MinterQuotaExceeded {
+        minter: "farm".to_string(),
+        remaining: Uint128::from(400u128),
+        amount: Uint128::from(500u128),
+    });
+    mint(deps.as_mut(), "farm", "bob", 400).unwrap();
+    assert_eq!(balance(deps.as_ref(), "bob"), Uint128::from(1000u128));
+
+    // the instantiate minter only has the global bound
+    mint(deps.as_mut(), "minter", "bob", 5000).unwrap();
+    let res = minters(deps.as_ref());
+    assert_eq!(res.total_supply, Uint128::from(106000u128));
+    assert_eq!(res.minters, vec![
+        MinterInfo {
+            minter: "farm".to_string(),
+            quota: Some(Uint128::from(1000u128)),
+            minted: Uint128::from(1000u128),
+            remaining_quota: Some(Uint128::zero()),
+        },
+        MinterInfo { minter: "minter".to_string(), quota: None, minted: Uint128::from(5000u128), remaining_quota: None },
+    ]);
+}
+
+#[test]
+fn supply_cap_bounds_every_minter() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        name: "DFC Token".to_string(),
+        symbol: "DFC".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin { address: "alice".to_string(), amount: Uint128::from(1000u128) }],
+        mint: Some(MinterResponse { minter: "minter".to_string(), cap: Some(Uint128::from(2000u128)) }),
+        marketing: None,
+        dflunc_address: None,
+        fomodfc_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    let msg = ExecuteMsg::AddMinter { minter: "farm".to_string(), quota: None };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    mint(deps.as_mut(), "minter", "bob", 600).unwrap();
+    let err = mint(deps.as_mut(), "farm", "bob", 500).unwrap_err();
+    assert_eq!(err, ContractError::SupplyCapExceeded { cap: Uint128::from(2000u128), amount: Uint128::from(500u128) });
+    mint(deps.as_mut(), "farm", "bob", 400).unwrap();
+    assert_eq!(total_supply(deps.as_ref()), Uint128::from(2000u128));
+}
+
+#[test]
+fn removed_and_renounced_minters_can_not_mint() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_token(deps.as_mut(), None, None);
+    let msg = ExecuteMsg::AddMinter { minter: "farm".to_string(), quota: None };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::RemoveMinter { minter: "farm".to_string() };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("minter", &[]), ExecuteMsg::RenounceMinter {}).unwrap();
+
+    for minter in ["farm", "minter"] {
+        let err = mint(deps.as_mut(), minter, "bob", 1).unwrap_err();
+        assert_eq!(err, ContractError::NotMinter { sender: minter.to_string() });
+    }
+    assert!(minters(deps.as_ref()).minters.is_empty());
+}
+
+fn env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+fn balance_at(deps: Deps, address: &str, height: u64) -> Uint128 {
+    let msg = QueryMsg::BalanceAt { address: address.to_string(), height };
+    let res: BalanceResponse = from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+    res.balance
+}
+
+fn total_supply_at(deps: Deps, height: u64) -> Uint128 {
+    let res: TotalSupplyResponse = from_binary(&query(deps, mock_env(), QueryMsg::TotalSupplyAt { height }).unwrap()).unwrap();
+    res.total_supply
+}
+
+#[test]
+fn balances_and_total_supply_are_checkpointed() {
+    let mut deps = mock_dependencies(&[]);
+    let start = mock_env().block.height;
+    instantiate_token(deps.as_mut(), None, None);
+    approve(deps.as_mut(), "alice", "spender", 5000);
+
+    let msg = ExecuteMsg::Transfer { recipient: "bob".to_string(), amount: Uint128::from(10000u128) };
+    execute(deps.as_mut(), env_at(start + 10), mock_info("alice", &[]), msg).unwrap();
+    let msg = ExecuteMsg::Mint { recipient: "bob".to_string(), amount: Uint128::from(500u128) };
+    execute(deps.as_mut(), env_at(start + 20), mock_info("minter", &[]), msg).unwrap();
+    let msg = ExecuteMsg::Burn { amount: Uint128::from(2000u128) };
+    execute(deps.as_mut(), env_at(start + 30), mock_info("bob", &[]), msg).unwrap();
+    let msg = ExecuteMsg::BurnFrom { owner: "alice".to_string(), amount: Uint128::from(1000u128) };
+    execute(deps.as_mut(), env_at(start + 40), mock_info("spender", &[]), msg).unwrap();
+    let msg = ExecuteMsg::TransferFrom {
+        owner: "alice".to_string(),
+        recipient: "carol".to_string(),
+        amount: Uint128::from(3000u128),
+    };
+    execute(deps.as_mut(), env_at(start + 40), mock_info("spender", &[]), msg).unwrap();
+
+    assert_eq!(balance_at(deps.as_ref(), "alice", start), Uint128::from(100000u128));
+    assert_eq!(balance_at(deps.as_ref(), "alice", start + 15), Uint128::from(90000u128));
+    assert_eq!(balance_at(deps.as_ref(), "alice", start + 40), Uint128::from(86000u128));
+    assert_eq!(balance_at(deps.as_ref(), "bob", start + 9), Uint128::zero());
+    assert_eq!(balance_at(deps.as_ref(), "bob", start + 10), Uint128::from(10000u128));
+    assert_eq!(balance_at(deps.as_ref(), "bob", start + 25), Uint128::from(10500u128));
+    assert_eq!(balance_at(deps.as_ref(), "bob", start + 30), Uint128::from(8500u128));
+    assert_eq!(balance_at(deps.as_ref(), "carol", start + 39), Uint128::zero());
+    assert_eq!(balance_at(deps.as_ref(), "carol", start + 100), Uint128::from(3000u128));
+
+    assert_eq!(total_supply_at(deps.as_ref(), start + 19), Uint128::from(100000u128));
+    assert_eq!(total_supply_at(deps.as_ref(), start + 20), Uint128::from(100500u128));
+    assert_eq!(total_supply_at(deps.as_ref(), start + 30), Uint128::from(98500u128));
+    assert_eq!(total_supply_at(deps.as_ref(), start + 40), Uint128::from(97500u128));
+}
+
+#[test]
+fn heights_before_the_history_are_rejected() {
+    let mut deps = mock_dependencies(&[]);
+    let start = mock_env().block.height;
+    instantiate_token(deps.as_mut(), None, None);
+
+    let msg = QueryMsg::BalanceAt { address: "alice".to_string(), height: start - 1 };
+    let err = query(deps.as_ref(), mock_env(), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err(format!("Balance history starts at height {}", start)));
+    let err = query(deps.as_ref(), mock_env(), QueryMsg::TotalSupplyAt { height: start - 1 }).unwrap_err();
+    assert_eq!(err, StdError::generic_err(format!("Balance history starts at height {}", start)));
+}
+
+#[test]
+fn history_of_a_migrated_token_starts_at_the_migration() {
+    let mut deps = mock_dependencies(&[]);
+    let start = mock_env().block.height;
+    let msg = cw20_base::msg::InstantiateMsg {
+        name: "DFC Token".to_string(),
+        symbol: "DFC".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin { address: "alice".to_string(), amount: Uint128::from(100000u128) }],
+        mint: Some(MinterResponse { minter: "minter".to_string(), cap: None }),
+        marketing: None,
+    };
+    cw20_base::contract::instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    let msg = MigrateMsg { admin: None, dflunc_address: None, fomodfc_address: None };
+    migrate(deps.as_mut(), env_at(start + 10), msg).unwrap();
+
+    let msg = ExecuteMsg::Transfer { recipient: "bob".to_string(), amount: Uint128::from(10000u128) };
+    execute(deps.as_mut(), env_at(start + 20), mock_info("alice", &[]), msg).unwrap();
+
+    assert_eq!(balance_at(deps.as_ref(), "alice", start + 10), Uint128::from(100000u128));
+    assert_eq!(balance_at(deps.as_ref(), "alice", start + 20), Uint128::from(90000u128));
+    assert_eq!(total_supply_at(deps.as_ref(), start + 10), Uint128::from(100000u128));
+    assert!(query(deps.as_ref(), mock_env(), QueryMsg::TotalSupplyAt { height: start + 9 }).is_err());
+    // the cw20 minter becomes admin of the migrated token
+    assert_eq!(minters(deps.as_ref()).admin, "minter".to_string());
+}
+
+// MockApi only humanizes its own padded canonical addresses, permit owners are 20 byte hashes
+#[derive(Default)]
+struct PermitApi(MockApi);
+
+impl Api for PermitApi {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        self.0.addr_validate(human)
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        self.0.addr_canonicalize(human)
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        if canonical.len() == 20 {
+            return Ok(Addr::unchecked(hex::encode(canonical.as_slice())));
+        }
+        self.0.addr_humanize(canonical)
+    }
+
+    fn secp256k1_verify(&self, message_hash: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, VerificationError> {
+        self.0.secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        self.0.secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, VerificationError> {
+        self.0.ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        self.0.ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        self.0.debug(message)
+    }
+}
+
+fn permit_dependencies() -> OwnedDeps<MockStorage, PermitApi, MockQuerier> {
+    OwnedDeps { storage: MockStorage::default(), api: PermitApi::default(), querier: MockQuerier::new(&[]) }
+}
+
+fn signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32]).unwrap()
+}
+
+// the account controlled by `signing_key`, as humanized by `PermitApi`
+fn permit_owner() -> String {
+    let pubkey = signing_key().verifying_key().to_bytes();
+    hex::encode(Ripemd160::digest(&Sha256::digest(pubkey.as_slice())))
+}
+
+fn permit_payload(nonce: u64, expires: Expiration) -> PermitPayload {
+    let env = mock_env();
+    PermitPayload {
+        chain_id: env.block.chain_id,
+        contract: env.contract.address.to_string(),
+        owner_pubkey: Binary::from(signing_key().verifying_key().to_bytes().as_slice()),
+        spender: "spender".to_string(),
+        amount: Uint128::from(5000u128),
+        expires,
+        nonce,
+    }
+}
+
+fn sign(payload: PermitPayload) -> ExecuteMsg {
+    let signature: Signature = signing_key().sign(&to_vec(&payload).unwrap());
+    ExecuteMsg::Permit { payload, signature: Binary::from(signature.as_ref()) }
+}
+
+fn permit_nonce(deps: Deps, owner: &str) -> u64 {
+    let msg = QueryMsg::GetPermitNonce { owner: owner.to_string() };
+    let res: GetPermitNonceResponse = from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+    res.nonce
+}
+
+#[test]
+fn permit_sets_the_allowance_of_the_signing_owner() {
+    let mut deps = permit_dependencies();
+    let owner = permit_owner();
+    let msg = InstantiateMsg {
+        name: "DFC Token".to_string(),
+        symbol: "DFC".to_string(),
+        decimals: 6,
+        initial_balances: vec![Cw20Coin { address: owner.clone(), amount: Uint128::from(100000u128) }],
+        mint: None,
+        marketing: None,
+        dflunc_address: None,
+        fomodfc_address: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    // anyone can relay the signed payload
+    let expires = Expiration::AtHeight(mock_env().block.height + 100);
+    execute(deps.as_mut(), mock_env(), mock_info("relayer", &[]), sign(permit_payload(0, expires))).unwrap();
+    assert_eq!(permit_nonce(deps.as_ref(), &owner), 1);
+
+    let msg = QueryMsg::Allowance { owner: owner.clone(), spender: "spender".to_string() };
+    let res: AllowanceResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res, AllowanceResponse { allowance: Uint128::from(5000u128), expires });
+
+    let msg = ExecuteMsg::TransferFrom { owner: owner.clone(), recipient: "bob".to_string(), amount: Uint128::from(5000u128) };
+    execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap();
+    assert_eq!(balance(deps.as_ref(), "bob"), Uint128::from(5000u128));
+}
+
+#[test]
+fn permit_rejects_replays_and_invalid_payloads() {
+    let mut deps = permit_dependencies();
+    instantiate_token(deps.as_mut(), None, None);
+    let env = mock_env();
+
+    execute(deps.as_mut(), env.clone(), mock_info("relayer", &[]), sign(permit_payload(0, Expiration::Never {}))).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info("relayer", &[]), sign(permit_payload(0, Expiration::Never {})))
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidPermitNonce { expected: 1, nonce: 0 });
+
+    let expires = Expiration::AtHeight(env.block.height);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("relayer", &[]), sign(permit_payload(1, expires))).unwrap_err();
+    assert_eq!(err, ContractError::PermitExpired { expires: expires.to_string() });
+
+    let mut payload = permit_payload(1, Expiration::Never {});
+    payload.chain_id = "other-chain".to_string();
+    let err = execute(deps.as_mut(), env.clone(), mock_info("relayer", &[]), sign(payload)).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPermitDomain {
+        chain_id: "other-chain".to_string(),
+        contract: env.contract.address.to_string(),
+    });
+
+    // a payload changed after signing doesn't match the signature
+    let msg = match sign(permit_payload(1, Expiration::Never {})) {
+        ExecuteMsg::Permit { mut payload, signature } => {
+            payload.amount = Uint128::from(1_000_000u128);
+            ExecuteMsg::Permit { payload, signature }
+        }
+        _ => unreachable!(),
+    };
+    let err = execute(deps.as_mut(), env, mock_info("relayer", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPermitSignature {});
+    assert_eq!(permit_nonce(deps.as_ref(), &permit_owner()), 1);
+}
+
+#[test]
+fn frozen_addresses_can_not_move_tokens() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_token(deps.as_mut(), None, None);
+    approve(deps.as_mut(), "alice", "spender", 5000);
+    let msg = ExecuteMsg::Freeze { address: "alice".to_string() };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("bob", &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::NotAdmin { sender: "bob".to_string(), admin: "admin".to_string() });
+    let res = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    assert_eq!(res.attributes[0].value, "freeze");
+
+    let frozen = ContractError::AddressFrozen { address: "alice".to_string() };
+    let msg = ExecuteMsg::Transfer { recipient: "bob".to_string(), amount: Uint128::from(100u128) };
+    assert_eq!(execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap_err(), frozen);
+    let msg = ExecuteMsg::Send { contract: "vault".to_string(), amount: Uint128::from(100u128), msg: Binary::default() };
+    assert_eq!(execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap_err(), frozen);
+    let msg = ExecuteMsg::TransferFrom { owner: "alice".to_string(), recipient: "bob".to_string(), amount: Uint128::from(100u128) };
+    assert_eq!(execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap_err(), frozen);
+    let msg = ExecuteMsg::SendFrom {
+        owner: "alice".to_string(),
+        contract: "vault".to_string(),
+        amount: Uint128::from(100u128),
+        msg: Binary::default(),
+    };
+    assert_eq!(execute(deps.as_mut(), mock_env(), mock_info("spender", &[]), msg).unwrap_err(), frozen);
+
+    // rewards can still be minted to it and it can still burn
+    mint(deps.as_mut(), "minter", "alice", 1000).unwrap();
+    let msg = ExecuteMsg::Burn { amount: Uint128::from(500u128) };
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+    assert_eq!(balance(deps.as_ref(), "alice"), Uint128::from(100500u128));
+
+    let msg = QueryMsg::GetFrozenAddresses { start_after: None, limit: None };
+    let res: GetFrozenAddressesResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.addresses, vec!["alice".to_string()]);
+
+    let msg = ExecuteMsg::Unfreeze { address: "alice".to_string() };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    let msg = ExecuteMsg::Transfer { recipient: "bob".to_string(), amount: Uint128::from(100u128) };
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), msg).unwrap();
+    assert_eq!(balance(deps.as_ref(), "bob"), Uint128::from(100u128));
+}
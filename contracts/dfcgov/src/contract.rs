@@ -0,0 +1,86 @@
+use crate::error::ContractError;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult
+};
+
+use cw2::set_contract_version;
+
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::{Config, CONFIG, GovState};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:dfcgov";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const MAX_BPS: u64 = 100000;
+
+impl<'a> GovState<'a> {
+    pub fn instantiate(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> Result<Response, ContractError> {
+        if msg.quorum == 0 || msg.quorum > MAX_BPS || msg.threshold == 0 || msg.threshold > MAX_BPS {
+            return Err(ContractError::NotValidVotingRule {});
+        }
+        if msg.voting_period == 0 {
+            return Err(ContractError::NotValidVotingPeriod {});
+        }
+        if msg.execution_period == 0 {
+            return Err(ContractError::NotValidExecutionPeriod {});
+        }
+
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        let dflunc_address = deps.api.addr_canonicalize(msg.dflunc_address.as_str())?;
+        let fomodfc_address = deps.api.addr_canonicalize(msg.fomodfc_address.as_str())?;
+        CONFIG.save(
+            deps.storage,
+            &Config {
+                dflunc_address,
+                fomodfc_address,
+                quorum: msg.quorum,
+                threshold: msg.threshold,
+                voting_period: msg.voting_period,
+                execution_period: msg.execution_period,
+            },
+        )?;
+        self.proposal_count.save(deps.storage, &0)?;
+        Ok(Response::new()
+            .add_attribute("method", "instantiate")
+            .add_attribute("owner", info.sender))
+    }
+
+    pub fn execute(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> Result<Response, ContractError> {
+        match msg {
+            ExecuteMsg::CreateProposal {
+                title,
+                description,
+                messages,
+            } => self.create_proposal(deps, env, info, title, description, messages),
+            ExecuteMsg::CastVote { proposal_id, vote } => self.cast_vote(deps, env, info, proposal_id, vote),
+            ExecuteMsg::ExecuteProposal { proposal_id } => self.execute_proposal(deps, env, info, proposal_id),
+        }
+    }
+
+    pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::GetConfig {  } => to_binary(&self.query_config(deps)?),
+            QueryMsg::GetProposal { proposal_id } => to_binary(&self.query_proposal(deps, env, proposal_id)?),
+            QueryMsg::GetProposals { start_after, limit } => to_binary(&self.query_proposals(deps, env, start_after, limit)?),
+            QueryMsg::GetVote { proposal_id, voter } => to_binary(&self.query_vote(deps, proposal_id, voter)?),
+            QueryMsg::GetVotingPower { user_address, cycle } => to_binary(&self.query_voting_power(deps, user_address, cycle)?),
+        }
+    }
+
+    pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+}
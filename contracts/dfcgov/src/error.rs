@@ -0,0 +1,45 @@
+use thiserror::Error;
+use cosmwasm_std::StdError;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Quorum and threshold should be in [1, 100000].")]
+    NotValidVotingRule {
+    },
+
+    #[error("Voting period should be greater than zero.")]
+    NotValidVotingPeriod {
+    },
+
+    #[error("Execution period should be greater than zero.")]
+    NotValidExecutionPeriod {
+    },
+
+    #[error("Proposal has no messages.")]
+    EmptyProposal {
+    },
+
+    #[error("NoVotingPower: {voter} had no stake at cycle {cycle}.")]
+    NoVotingPower { voter: String, cycle: u64 },
+
+    #[error("AlreadyVoted: {voter} already voted on proposal {proposal_id}.")]
+    AlreadyVoted { voter: String, proposal_id: u64 },
+
+    #[error("VotingClosed: Voting on proposal {proposal_id} ended at {end_time}.")]
+    VotingClosed { proposal_id: u64, end_time: u64 },
+
+    #[error("VotingNotEnded: Voting on proposal {proposal_id} ends at {end_time}.")]
+    VotingNotEnded { proposal_id: u64, end_time: u64 },
+
+    #[error("NotPassed: Proposal {proposal_id} did not pass.")]
+    NotPassed { proposal_id: u64 },
+
+    #[error("AlreadyExecuted: Proposal {proposal_id} has been executed.")]
+    AlreadyExecuted { proposal_id: u64 },
+
+    #[error("ProposalExpired: Proposal {proposal_id} could be executed until {expires_at}.")]
+    ProposalExpired { proposal_id: u64, expires_at: u64 },
+}
@@ -0,0 +1,303 @@
+use crate::error::ContractError;
+use crate::state::{GovState, Config, Proposal, Vote, CONFIG};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Uint128, StdResult, Order,
+    CosmosMsg, WasmMsg, to_binary, Addr, QuerierWrapper, WasmQuery, QueryRequest};
+use cw_storage_plus::{Bound, U64Key};
+use std::convert::TryInto;
+use crate::msg::{
+    GetConfigResponse, GetProposalResponse, GetProposalsResponse, GetVoteResponse,
+    GetVotingPowerResponse, ProposalMsg, ProposalStatus, VoteOption
+};
+use dflunc::msg::{
+    ExecuteMsg as DfluncExecuteMsg, QueryMsg as DfluncQueryMsg,
+    GetConfigResponse as DfluncConfigResponse, GetStakeAtResponse
+};
+use fomodfc::msg::{ExecuteMsg as FomodfcExecuteMsg, UpdateConfigMsg as FomodfcUpdateConfigMsg};
+
+const MAX_BPS: u64 = 100000;
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+impl<'a> GovState<'a> {
+    pub fn create_proposal(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        title: String,
+        description: String,
+        messages: Vec<ProposalMsg>,
+    ) -> Result<Response, ContractError> {
+        if messages.is_empty() {
+            return Err(ContractError::EmptyProposal {});
+        }
+        let config = CONFIG.load(deps.storage)?;
+        let dflunc_address = deps.api.addr_humanize(&config.dflunc_address)?;
+
+        // the stake held when the current dflunc cycle started can't be moved into a vote anymore
+        let snapshot_cycle = self.get_dflunc_cycle(&deps.querier, &dflunc_address, env.block.time.seconds())?;
+        let power = self.get_stake_at(&deps.querier, &dflunc_address, &info.sender, snapshot_cycle)?;
+        if power.is_zero() {
+            return Err(ContractError::NoVotingPower { voter: info.sender.to_string(), cycle: snapshot_cycle });
+        }
+        let total_power = self.get_total_stake_at(&deps.querier, &dflunc_address, snapshot_cycle)?;
+
+        let proposal_id = self.proposal_count.may_load(deps.storage)?.unwrap_or(0) + 1;
+        let end_time = env.block.time.seconds() + config.voting_period;
+        self.proposals.save(deps.storage, U64Key::from(proposal_id), &Proposal {
+            proposer: info.sender.clone(),
+            title,
+            description,
+            messages,
+            snapshot_cycle,
+            total_power,
+            end_time,
+            yes_votes: Uint128::zero(),
+            no_votes: Uint128::zero(),
+            abstain_votes: Uint128::zero(),
+            executed: false,
+        })?;
+        self.proposal_count.save(deps.storage, &proposal_id)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "create_proposal")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("proposer", info.sender)
+            .add_attribute("snapshot_cycle", snapshot_cycle.to_string())
+            .add_attribute("end_time", end_time.to_string()))
+    }
+
+    pub fn cast_vote(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        proposal_id: u64,
+        vote: VoteOption,
+    ) -> Result<Response, ContractError> {
+        let mut proposal = self.proposals.load(deps.storage, U64Key::from(proposal_id))?;
+        if env.block.time.seconds() >= proposal.end_time {
+            return Err(ContractError::VotingClosed { proposal_id, end_time: proposal.end_time });
+        }
+        if self.votes.may_load(deps.storage, (U64Key::from(proposal_id), info.sender.clone()))?.is_some() {
+            return Err(ContractError::AlreadyVoted { voter: info.sender.to_string(), proposal_id });
+        }
+
+        let config = CONFIG.load(deps.storage)?;
+        let dflunc_address = deps.api.addr_humanize(&config.dflunc_address)?;
+        let power = self.get_stake_at(&deps.querier, &dflunc_address, &info.sender, proposal.snapshot_cycle)?;
+        if power.is_zero() {
+            return Err(ContractError::NoVotingPower { voter: info.sender.to_string(), cycle: proposal.snapshot_cycle });
+        }
+
+        match vote {
+            VoteOption::Yes => proposal.yes_votes += power,
+            VoteOption::No => proposal.no_votes += power,
+            VoteOption::Abstain => proposal.abstain_votes += power,
+        }
+        self.proposals.save(deps.storage, U64Key::from(proposal_id), &proposal)?;
+        self.votes.save(deps.storage, (U64Key::from(proposal_id), info.sender.clone()), &Vote {
+            option: vote,
+            power,
+        })?;
+
+        Ok(Response::new()
+            .add_attribute("action", "cast_vote")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("voter", info.sender)
+            .add_attribute("power", power.to_string()))
+    }
+
+    pub fn execute_proposal(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        _info: MessageInfo,
+        proposal_id: u64,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let mut proposal = self.proposals.load(deps.storage, U64Key::from(proposal_id))?;
+        match self.proposal_status(&config, &proposal, env.block.time.seconds()) {
+            ProposalStatus::Open => return Err(ContractError::VotingNotEnded { proposal_id, end_time: proposal.end_time }),
+            ProposalStatus::Rejected => return Err(ContractError::NotPassed { proposal_id }),
+            ProposalStatus::Executed => return Err(ContractError::AlreadyExecuted { proposal_id }),
+            ProposalStatus::Expired => return Err(ContractError::ProposalExpired {
+                proposal_id,
+                expires_at: proposal.end_time.saturating_add(config.execution_period),
+            }),
+            ProposalStatus::Passed => {}
+        }
+
+        let mut messages: Vec<CosmosMsg> = vec![];
+        for proposal_msg in proposal.messages.iter() {
+            let msg = match proposal_msg.clone() {
+                ProposalMsg::UpdateDfluncConfig {
+                    lunc_batch_amount,
+                    protocol_fees_reserved_rate,
+                } => WasmMsg::Execute {
+                    contract_addr: deps.api.addr_humanize(&config.dflunc_address)?.to_string(),
+                    msg: to_binary(&DfluncExecuteMsg::UpdateConfig {
+                        lunc_batch_amount,
+                        ustc_batch_amount: None,
+                        protocol_fees_reserved_rate,
+                    })?,
+                    funds: vec![],
+                },
+                ProposalMsg::UpdateFomodfcConfig {
+                    dividend_percent,
+                    burned_percent,
+                    invite_percent,
+                    dev_percent,
+                    max_delay_time,
+                } => WasmMsg::Execute {
+                    contract_addr: deps.api.addr_humanize(&config.fomodfc_address)?.to_string(),
+                    msg: to_binary(&FomodfcExecuteMsg::UpdateConfig(Box::new(FomodfcUpdateConfigMsg {
+                        dividend_percent,
+                        burned_percent,
+                        invite_percent,
+                        dev_percent,
+                        max_delay_time,
+                        ..FomodfcUpdateConfigMsg::default()
+                    })))?,
+                    funds: vec![],
+                },
+            };
+            messages.push(CosmosMsg::Wasm(msg));
+        }
+
+        proposal.executed = true;
+        self.proposals.save(deps.storage, U64Key::from(proposal_id), &proposal)?;
+
+        Ok(Response::new()
+            .add_messages(messages)
+            .add_attribute("action", "execute_proposal")
+            .add_attribute("proposal_id", proposal_id.to_string()))
+    }
+
+    pub fn query_config(&self, deps: Deps) -> StdResult<GetConfigResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        Ok(GetConfigResponse {
+            dflunc_address: deps.api.addr_humanize(&config.dflunc_address)?.to_string(),
+            fomodfc_address: deps.api.addr_humanize(&config.fomodfc_address)?.to_string(),
+            quorum: config.quorum,
+            threshold: config.threshold,
+            voting_period: config.voting_period,
+            execution_period: config.execution_period,
+        })
+    }
+
+    pub fn query_proposal(&self, deps: Deps, env: Env, proposal_id: u64) -> StdResult<GetProposalResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let proposal = self.proposals.load(deps.storage, U64Key::from(proposal_id))?;
+        Ok(self.to_proposal_response(&config, proposal_id, proposal, env.block.time.seconds()))
+    }
+
+    pub fn query_proposals(
+        &self,
+        deps: Deps,
+        env: Env,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<GetProposalsResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|id| Bound::exclusive(U64Key::from(id)));
+        let current_time = env.block.time.seconds();
+
+        let proposals = self.proposals
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (key, proposal) = item?;
+                let proposal_id = u64::from_be_bytes(key.as_slice().try_into().unwrap_or_default());
+                Ok(self.to_proposal_response(&config, proposal_id, proposal, current_time))
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(GetProposalsResponse { proposals })
+    }
+
+    pub fn query_vote(&self, deps: Deps, proposal_id: u64, voter: String) -> StdResult<GetVoteResponse> {
+        let voter = deps.api.addr_validate(voter.as_str())?;
+        let vote = self.votes.may_load(deps.storage, (U64Key::from(proposal_id), voter))?;
+        Ok(match vote {
+            Some(vote) => GetVoteResponse { vote: Some(vote.option), power: vote.power },
+            None => GetVoteResponse { vote: None, power: Uint128::zero() },
+        })
+    }
+
+    pub fn query_voting_power(&self, deps: Deps, user_address: String, cycle: u64) -> StdResult<GetVotingPowerResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let dflunc_address = deps.api.addr_humanize(&config.dflunc_address)?;
+        let user_address = deps.api.addr_validate(user_address.as_str())?;
+        let power = self.get_stake_at(&deps.querier, &dflunc_address, &user_address, cycle)?;
+        Ok(GetVotingPowerResponse { power })
+    }
+
+    fn to_proposal_response(&self, config: &Config, proposal_id: u64, proposal: Proposal, current_time: u64) -> GetProposalResponse {
+        let status = self.proposal_status(config, &proposal, current_time);
+        GetProposalResponse {
+            proposal_id,
+            proposer: proposal.proposer.to_string(),
+            title: proposal.title,
+            description: proposal.description,
+            messages: proposal.messages,
+            snapshot_cycle: proposal.snapshot_cycle,
+            total_power: proposal.total_power,
+            end_time: proposal.end_time,
+            yes_votes: proposal.yes_votes,
+            no_votes: proposal.no_votes,
+            abstain_votes: proposal.abstain_votes,
+            status,
+        }
+    }
+
+    fn proposal_status(&self, config: &Config, proposal: &Proposal, current_time: u64) -> ProposalStatus {
+        if proposal.executed {
+            return ProposalStatus::Executed;
+        }
+        if current_time < proposal.end_time {
+            return ProposalStatus::Open;
+        }
+        let expired = current_time >= proposal.end_time.saturating_add(config.execution_period);
+        let voted = proposal.yes_votes + proposal.no_votes + proposal.abstain_votes;
+        let quorum_reached = voted.full_mul(MAX_BPS) >= proposal.total_power.full_mul(config.quorum);
+        let threshold_reached = proposal.yes_votes.full_mul(MAX_BPS)
+            > (proposal.yes_votes + proposal.no_votes).full_mul(config.threshold);
+        if quorum_reached && threshold_reached && !proposal.total_power.is_zero() {
+            if expired {
+                ProposalStatus::Expired
+            } else {
+                ProposalStatus::Passed
+            }
+        } else {
+            ProposalStatus::Rejected
+        }
+    }
+
+    fn get_dflunc_cycle(&self, querier: &QuerierWrapper, dflunc_address: &Addr, current_time: u64) -> StdResult<u64> {
+        let dflunc_config: DfluncConfigResponse = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: dflunc_address.to_string(),
+            msg: to_binary(&DfluncQueryMsg::GetConfig {})?,
+        }))?;
+        Ok(current_time.saturating_sub(dflunc_config.initial_timestamp) / dflunc_config.period_duration)
+    }
+
+    fn get_stake_at(&self, querier: &QuerierWrapper, dflunc_address: &Addr, user_address: &Addr, cycle: u64) -> StdResult<Uint128> {
+        let stake: GetStakeAtResponse = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: dflunc_address.to_string(),
+            msg: to_binary(&DfluncQueryMsg::GetStakeAt {
+                user_address: user_address.to_string(),
+                cycle,
+            })?,
+        }))?;
+        Ok(stake.amount)
+    }
+
+    fn get_total_stake_at(&self, querier: &QuerierWrapper, dflunc_address: &Addr, cycle: u64) -> StdResult<Uint128> {
+        let stake: GetStakeAtResponse = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: dflunc_address.to_string(),
+            msg: to_binary(&DfluncQueryMsg::GetTotalStakeAt { cycle })?,
+        }))?;
+        Ok(stake.amount)
+    }
+}
@@ -0,0 +1,85 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, ContractResult, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use std::collections::HashMap;
+
+use dflunc::msg::{GetConfigResponse as DfluncConfigResponse, GetStakeAtResponse, QueryMsg as DfluncQueryMsg};
+
+pub const INITIAL_TIMESTAMP: u64 = 1_000_000;
+pub const PERIOD_DURATION: u64 = 86400;
+
+/// mock_dependencies answering the dflunc queries of the governance from a fixed stake table
+pub fn mock_dependencies() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: WasmMockQuerier::new(MockQuerier::new(&[])),
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier,
+    // stake per user at any cycle after the first one, nothing before
+    stakes: HashMap<String, Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<_> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier) -> Self {
+        WasmMockQuerier {
+            base,
+            stakes: HashMap::new(),
+        }
+    }
+
+    pub fn with_stakes(&mut self, stakes: &[(&str, u128)]) {
+        self.stakes = stakes
+            .iter()
+            .map(|(user, amount)| (user.to_string(), Uint128::from(*amount)))
+            .collect();
+    }
+
+    pub fn handle_query(&self, request: &QueryRequest<cosmwasm_std::Empty>) -> QuerierResult {
+        match request {
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. }) => {
+                let response = match from_binary(msg).unwrap() {
+                    DfluncQueryMsg::GetConfig {} => to_binary(&DfluncConfigResponse {
+                        dfc_address: "dfc".to_string(),
+                        lunc_batch_amount: Uint128::zero(),
+                        ustc_batch_amount: Uint128::zero(),
+                        initial_timestamp: INITIAL_TIMESTAMP,
+                        ustc_claimer_address: "claimer".to_string(),
+                        owner: "owner".to_string(),
+                        protocol_fees_reserved_rate: 0,
+                        period_duration: PERIOD_DURATION,
+                    }),
+                    DfluncQueryMsg::GetStakeAt { user_address, cycle } => to_binary(&GetStakeAtResponse {
+                        amount: if cycle > 0 { self.stakes.get(&user_address).cloned().unwrap_or_default() } else { Uint128::zero() },
+                    }),
+                    DfluncQueryMsg::GetTotalStakeAt { cycle } => to_binary(&GetStakeAtResponse {
+                        amount: if cycle > 0 { self.stakes.values().sum() } else { Uint128::zero() },
+                    }),
+                    _ => panic!("DO NOT ENTER HERE"),
+                };
+                SystemResult::Ok(ContractResult::from(response))
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use cosmwasm_std::Uint128;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub dflunc_address: String,
+    pub fomodfc_address: String,
+    pub quorum: u64,
+    pub threshold: u64,
+    pub voting_period: u64,
+    // seconds after the vote a passed proposal can still be executed
+    pub execution_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    CreateProposal {
+        title: String,
+        description: String,
+        messages: Vec<ProposalMsg>,
+    },
+    CastVote {
+        proposal_id: u64,
+        vote: VoteOption,
+    },
+    ExecuteProposal {
+        proposal_id: u64,
+    },
+}
+
+// the only messages a proposal is allowed to send, limited to economic parameters,
+// fields left as None keep their current value
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalMsg {
+    UpdateDfluncConfig {
+        lunc_batch_amount: Option<Uint128>,
+        protocol_fees_reserved_rate: Option<u64>,
+    },
+    // the percents have to keep summing up to 100000 with the dividend percent
+    UpdateFomodfcConfig {
+        dividend_percent: Option<u64>,
+        burned_percent: Option<u64>,
+        invite_percent: Option<u64>,
+        dev_percent: Option<u64>,
+        max_delay_time: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteOption {
+    Yes,
+    No,
+    Abstain,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Open,
+    Passed,
+    Rejected,
+    Executed,
+    // passed but not executed within the execution period
+    Expired,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    GetConfig {},
+    GetProposal {
+        proposal_id: u64,
+    },
+    GetProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    GetVote {
+        proposal_id: u64,
+        voter: String,
+    },
+    GetVotingPower {
+        user_address: String,
+        cycle: u64,
+    },
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetConfigResponse {
+    pub dflunc_address: String,
+    pub fomodfc_address: String,
+    pub quorum: u64,
+    pub threshold: u64,
+    pub voting_period: u64,
+    pub execution_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetProposalResponse {
+    pub proposal_id: u64,
+    pub proposer: String,
+    pub title: String,
+    pub description: String,
+    pub messages: Vec<ProposalMsg>,
+    pub snapshot_cycle: u64,
+    pub total_power: Uint128,
+    pub end_time: u64,
+    pub yes_votes: Uint128,
+    pub no_votes: Uint128,
+    pub abstain_votes: Uint128,
+    pub status: ProposalStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetProposalsResponse {
+    pub proposals: Vec<GetProposalResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetVoteResponse {
+    pub vote: Option<VoteOption>,
+    pub power: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetVotingPowerResponse {
+    pub power: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
@@ -0,0 +1,56 @@
+use cosmwasm_std::{CanonicalAddr, Uint128, Addr};
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::msg::{ProposalMsg, VoteOption};
+
+pub struct GovState<'a> {
+    pub proposal_count: Item<'a, u64>,
+    pub proposals: Map<'a, U64Key, Proposal>,
+    pub votes: Map<'a, (U64Key, Addr), Vote>,
+}
+
+impl Default for GovState<'static> {
+    fn default() -> Self {
+        Self {
+            proposal_count: Item::new("proposal_count"),
+            proposals: Map::new("proposals"),
+            votes: Map::new("votes"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub dflunc_address: CanonicalAddr,
+    pub fomodfc_address: CanonicalAddr,
+    pub quorum: u64,        // share of the total stake that has to vote
+    pub threshold: u64,     // share of yes in yes + no votes
+    pub voting_period: u64, // seconds
+    pub execution_period: u64, // seconds after end_time
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposal {
+    pub proposer: Addr,
+    pub title: String,
+    pub description: String,
+    pub messages: Vec<ProposalMsg>,
+    // voting power is the dflunc stake held when this cycle started
+    pub snapshot_cycle: u64,
+    pub total_power: Uint128,
+    pub end_time: u64,
+    pub yes_votes: Uint128,
+    pub no_votes: Uint128,
+    pub abstain_votes: Uint128,
+    pub executed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Vote {
+    pub option: VoteOption,
+    pub power: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("CONFIG");
@@ -0,0 +1,142 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_binary, to_binary, CosmosMsg, DepsMut, Env, Timestamp, Uint128, WasmMsg};
+
+use crate::error::ContractError;
+use crate::mock_querier::mock_dependencies;
+use crate::msg::{
+    ExecuteMsg, GetProposalResponse, GetVotingPowerResponse, InstantiateMsg, ProposalMsg,
+    ProposalStatus, QueryMsg, VoteOption,
+};
+use crate::state::GovState;
+use fomodfc::msg::{ExecuteMsg as FomodfcExecuteMsg, UpdateConfigMsg as FomodfcUpdateConfigMsg};
+
+const START_TIME: u64 = 2_000_000;
+
+fn env_at(time: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(time);
+    env
+}
+
+fn instantiate(deps: DepsMut) {
+    let msg = InstantiateMsg {
+        dflunc_address: "dflunc".to_string(),
+        fomodfc_address: "fomodfc".to_string(),
+        quorum: 30000,
+        threshold: 50000,
+        voting_period: 100,
+        execution_period: 100,
+    };
+    GovState::default().instantiate(deps, env_at(START_TIME), mock_info("creator", &[]), msg).unwrap();
+}
+
+fn fomodfc_proposal() -> Vec<ProposalMsg> {
+    vec![ProposalMsg::UpdateFomodfcConfig {
+        dividend_percent: Some(50000),
+        burned_percent: Some(30000),
+        invite_percent: None,
+        dev_percent: None,
+        max_delay_time: Some(3600),
+    }]
+}
+
+fn execute(deps: DepsMut, time: u64, sender: &str, msg: ExecuteMsg) -> Result<cosmwasm_std::Response, ContractError> {
+    GovState::default().execute(deps, env_at(time), mock_info(sender, &[]), msg)
+}
+
+fn create_proposal(deps: DepsMut, sender: &str) -> Result<cosmwasm_std::Response, ContractError> {
+    execute(deps, START_TIME, sender, ExecuteMsg::CreateProposal {
+        title: "fomodfc split".to_string(),
+        description: "burn more".to_string(),
+        messages: fomodfc_proposal(),
+    })
+}
+
+fn vote(deps: DepsMut, time: u64, sender: &str, vote: VoteOption) -> Result<cosmwasm_std::Response, ContractError> {
+    execute(deps, time, sender, ExecuteMsg::CastVote { proposal_id: 1, vote })
+}
+
+fn query_proposal(deps: cosmwasm_std::Deps, time: u64) -> GetProposalResponse {
+    let res = GovState::default().query(deps, env_at(time), QueryMsg::GetProposal { proposal_id: 1 }).unwrap();
+    from_binary(&res).unwrap()
+}
+
+#[test]
+fn proposal_lifecycle() {
+    let mut deps = mock_dependencies();
+    deps.querier.with_stakes(&[("alice", 60), ("bob", 30), ("carol", 10)]);
+    instantiate(deps.as_mut());
+
+    let err = create_proposal(deps.as_mut(), "dave").unwrap_err();
+    assert!(matches!(err, ContractError::NoVotingPower { .. }));
+    create_proposal(deps.as_mut(), "alice").unwrap();
+
+    // the power is the dflunc stake at the snapshot cycle
+    let proposal = query_proposal(deps.as_ref(), START_TIME);
+    assert_eq!(proposal.total_power, Uint128::from(100u128));
+    let res = GovState::default().query(deps.as_ref(), env_at(START_TIME), QueryMsg::GetVotingPower {
+        user_address: "bob".to_string(),
+        cycle: proposal.snapshot_cycle,
+    }).unwrap();
+    let power: GetVotingPowerResponse = from_binary(&res).unwrap();
+    assert_eq!(power.power, Uint128::from(30u128));
+
+    vote(deps.as_mut(), START_TIME + 10, "alice", VoteOption::Yes).unwrap();
+    let err = vote(deps.as_mut(), START_TIME + 10, "alice", VoteOption::No).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyVoted { voter: "alice".to_string(), proposal_id: 1 });
+    vote(deps.as_mut(), START_TIME + 20, "bob", VoteOption::No).unwrap();
+
+    let err = execute(deps.as_mut(), START_TIME + 50, "anyone", ExecuteMsg::ExecuteProposal { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::VotingNotEnded { proposal_id: 1, end_time: START_TIME + 100 });
+    let err = vote(deps.as_mut(), START_TIME + 100, "carol", VoteOption::No).unwrap_err();
+    assert_eq!(err, ContractError::VotingClosed { proposal_id: 1, end_time: START_TIME + 100 });
+
+    let proposal = query_proposal(deps.as_ref(), START_TIME + 100);
+    assert_eq!(proposal.yes_votes, Uint128::from(60u128));
+    assert_eq!(proposal.no_votes, Uint128::from(30u128));
+    assert_eq!(proposal.status, ProposalStatus::Passed);
+
+    // only the whitelisted parameters reach fomodfc
+    let res = execute(deps.as_mut(), START_TIME + 150, "anyone", ExecuteMsg::ExecuteProposal { proposal_id: 1 }).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "fomodfc".to_string(),
+        msg: to_binary(&FomodfcExecuteMsg::UpdateConfig(Box::new(FomodfcUpdateConfigMsg {
+            dividend_percent: Some(50000),
+            burned_percent: Some(30000),
+            max_delay_time: Some(3600),
+            ..FomodfcUpdateConfigMsg::default()
+        }))).unwrap(),
+        funds: vec![],
+    }));
+    assert_eq!(query_proposal(deps.as_ref(), START_TIME + 150).status, ProposalStatus::Executed);
+    let err = execute(deps.as_mut(), START_TIME + 150, "anyone", ExecuteMsg::ExecuteProposal { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyExecuted { proposal_id: 1 });
+}
+
+#[test]
+fn proposal_without_quorum_is_rejected() {
+    let mut deps = mock_dependencies();
+    deps.querier.with_stakes(&[("alice", 60), ("bob", 30), ("carol", 10)]);
+    instantiate(deps.as_mut());
+    create_proposal(deps.as_mut(), "carol").unwrap();
+    vote(deps.as_mut(), START_TIME + 10, "carol", VoteOption::Yes).unwrap();
+
+    assert_eq!(query_proposal(deps.as_ref(), START_TIME + 100).status, ProposalStatus::Rejected);
+    let err = execute(deps.as_mut(), START_TIME + 100, "anyone", ExecuteMsg::ExecuteProposal { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::NotPassed { proposal_id: 1 });
+}
+
+#[test]
+fn passed_proposal_expires_after_execution_period() {
+    let mut deps = mock_dependencies();
+    deps.querier.with_stakes(&[("alice", 60), ("bob", 40)]);
+    instantiate(deps.as_mut());
+    create_proposal(deps.as_mut(), "alice").unwrap();
+    vote(deps.as_mut(), START_TIME + 10, "alice", VoteOption::Yes).unwrap();
+
+    assert_eq!(query_proposal(deps.as_ref(), START_TIME + 199).status, ProposalStatus::Passed);
+    assert_eq!(query_proposal(deps.as_ref(), START_TIME + 200).status, ProposalStatus::Expired);
+    let err = execute(deps.as_mut(), START_TIME + 200, "anyone", ExecuteMsg::ExecuteProposal { proposal_id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::ProposalExpired { proposal_id: 1, expires_at: START_TIME + 200 });
+}
@@ -1,8 +1,8 @@
-#[cfg(not(feature = "library"))]
 use crate::error::ContractError;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128
 };
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
 
 use cw2::set_contract_version;
 use cw_storage_plus::U64Key;
@@ -88,6 +88,12 @@ impl<'a> DFCState<'a> {
             ExecuteMsg::SetDfcAddress {
                 dfc_address,
             } => self.set_dfc_addr(deps, env, info, dfc_address),
+            ExecuteMsg::SetOwner { owner } => self.set_owner(deps, env, info, owner),
+            ExecuteMsg::UpdateConfig {
+                lunc_batch_amount,
+                ustc_batch_amount,
+                protocol_fees_reserved_rate,
+            } => self.update_config(deps, env, info, lunc_batch_amount, ustc_batch_amount, protocol_fees_reserved_rate),
         }
     }
     
@@ -101,10 +107,22 @@ impl<'a> DFCState<'a> {
             QueryMsg::GetAccWithdrawableStake { user_address } => to_binary(&self.query_acc_withdrawable_stake(deps, env, user_address)?),
             QueryMsg::GetUnclaimedRewards { user_address } => to_binary(&self.query_unclaimed_rewards(deps, env, user_address)?),
             QueryMsg::GetUnclaimedFees { user_address } => to_binary(&self.query_unclaimed_fees(deps, env, user_address)?),
+            QueryMsg::GetStakeAt { user_address, cycle } => to_binary(&self.query_stake_at(deps, user_address, cycle)?),
+            QueryMsg::GetTotalStakeAt { cycle } => to_binary(&self.query_total_stake_at(deps, cycle)?),
         }
     }
     
-    pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    pub fn migrate(&self, deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+        // the dfc held by the contract is what was staked before the snapshots were introduced
+        if self.total_stake_snapshots.keys(deps.storage, None, None, Order::Ascending).next().is_none() {
+            let config = CONFIG.load(deps.storage)?;
+            let base_state = self.base_state.load(deps.storage)?;
+            let staked: Cw20BalanceResponse = deps.querier.query_wasm_smart(
+                deps.api.addr_humanize(&config.dfc_address)?,
+                &Cw20QueryMsg::Balance { address: env.contract.address.to_string() },
+            )?;
+            self.total_stake_snapshots.save(deps.storage, U64Key::from(base_state.current_cycle), &staked.balance)?;
+        }
         Ok(Response::default())
     }
 }
\ No newline at end of file
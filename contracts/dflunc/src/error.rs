@@ -29,6 +29,10 @@ pub enum ContractError {
     NotValidBatchNumber {
     },
 
+    #[error("Protocol fees reserved rate should be in [0, 100000].")]
+    NotValidReservedRate {
+    },
+
     #[error("No reward.")]
     NoRewards {
     },
@@ -1,13 +1,14 @@
 use crate::error::ContractError;
 use crate::state::{DFCState, CONFIG, LUNC_DENOM, USTC_DENOM};
 use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Uint128, Coin, StdResult, StdError, 
-                   CosmosMsg, WasmMsg, to_binary, Storage, Addr, CanonicalAddr, BalanceResponse, BankQuery, QueryRequest};
-use cw_storage_plus::U64Key;
+                   CosmosMsg, WasmMsg, to_binary, Storage, Addr, CanonicalAddr, BalanceResponse, BankQuery, QueryRequest, Order};
+use cw_storage_plus::{Bound, U64Key};
+use std::convert::TryInto;
 use terraswap::asset::{Asset, AssetInfo};
 use cw20::Cw20ExecuteMsg;
 use crate::msg::{
     GetConfigResponse, GetBaseStateResponse, GetCycleInfoResponse, GetUserInfoResponse, GetWithdrawableStakeResponse,
-    GetUnclaimedRewardsResponse, GetCurrentCycleRewards, GetUnclaimedFees
+    GetUnclaimedRewardsResponse, GetCurrentCycleRewards, GetUnclaimedFees, GetStakeAtResponse
 };
 
 fn only_owner(deps: Deps, sender: CanonicalAddr) -> Result<bool, ContractError> {
@@ -247,6 +248,7 @@ impl<'a> DFCState<'a> {
                 Ok(acc_stake_cycle_before.unwrap_or_default() + amount)
             },
         )?;
+        self.update_stake_snapshots(deps.storage, info.sender.clone(), base_state.current_cycle, amount, true)?;
 
         let mut messages: Vec<CosmosMsg> = vec![];
         let config = CONFIG.load(deps.storage)?;
@@ -313,6 +315,7 @@ impl<'a> DFCState<'a> {
                                 |reward: Option<Uint128>| -> StdResult<_> {
                                     Ok(reward.unwrap_or_default().checked_sub(amount)?)
                                 })?;                               
+        self.update_stake_snapshots(deps.storage, info.sender.clone(), base_state.current_cycle, amount, false)?;
 
 
         let mut messages: Vec<CosmosMsg> = vec![];
@@ -380,6 +383,63 @@ impl<'a> DFCState<'a> {
         Ok(res)
     }
 
+    pub fn set_owner(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        owner: String
+    ) -> Result<Response, ContractError> {
+        let sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+        only_owner(deps.as_ref(), sender)?;
+        
+        let mut config = CONFIG.load(deps.storage)?;
+        config.owner = deps.api.addr_canonicalize(owner.as_str())?;
+        CONFIG.save(deps.storage, &config)?;
+    
+        let res = Response::new()
+            .add_attribute("action", "setOwner")
+            .add_attribute("owner", owner);
+    
+        Ok(res)
+    }
+
+    pub fn update_config(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        lunc_batch_amount: Option<Uint128>,
+        ustc_batch_amount: Option<Uint128>,
+        protocol_fees_reserved_rate: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        let sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+        only_owner(deps.as_ref(), sender)?;
+        
+        let mut config = CONFIG.load(deps.storage)?;
+        if let Some(lunc_batch_amount) = lunc_batch_amount {
+            config.lunc_batch_amount = lunc_batch_amount;
+        }
+        if let Some(ustc_batch_amount) = ustc_batch_amount {
+            config.ustc_batch_amount = ustc_batch_amount;
+        }
+        if let Some(protocol_fees_reserved_rate) = protocol_fees_reserved_rate {
+            if protocol_fees_reserved_rate > MAX_BPS {
+                return Err(ContractError::NotValidReservedRate {});
+            }
+            config.protocol_fees_reserved_rate = protocol_fees_reserved_rate;
+        }
+        CONFIG.save(deps.storage, &config)?;
+    
+        let res = Response::new()
+            .add_attribute("action", "updateConfig")
+            .add_attribute("lunc_batch_amount", config.lunc_batch_amount.to_string())
+            .add_attribute("ustc_batch_amount", config.ustc_batch_amount.to_string())
+            .add_attribute("protocol_fees_reserved_rate", config.protocol_fees_reserved_rate.to_string());
+    
+        Ok(res)
+    }
+
     pub fn claim_ustc_reserved_fees(
         &self,
         deps: DepsMut,
@@ -655,6 +715,69 @@ impl<'a> DFCState<'a> {
         Ok(Response::default())
    }
 
+    // dfc staked by the user, unlocked or still waiting for its stake cycle
+    fn staked_amount(&self, storage: &dyn Storage, user_addr: Addr) -> StdResult<Uint128> {
+        let mut amount = self.acc_withdrawable_stake.may_load(storage, user_addr.clone())?.unwrap_or_default();
+        for stake_cycle in [&self.acc_first_stake, &self.acc_second_stake] {
+            let stake_cycle = stake_cycle.may_load(storage, user_addr.clone())?.unwrap_or(0);
+            if stake_cycle != 0 {
+                amount += self.acc_stake_cycle.may_load(storage, (user_addr.clone(), U64Key::from(stake_cycle)))?.unwrap_or_default();
+            }
+        }
+        Ok(amount)
+    }
+
+    fn update_stake_snapshots(
+        &self,
+        storage: &mut dyn Storage,
+        user_addr: Addr,
+        cycle: u64,
+        amount: Uint128,
+        is_stake: bool,
+    ) -> StdResult<()> {
+        let last_user_stake = self.acc_stake_snapshots
+            .prefix(user_addr.clone())
+            .range(storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .map(|(_, stake)| stake);
+        let total_stake = self.total_stake_snapshots
+            .range(storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .map(|(_, stake)| stake)
+            .unwrap_or_default();
+
+        let user_stake = match last_user_stake {
+            Some(user_stake) if is_stake => user_stake + amount,
+            Some(user_stake) => user_stake.saturating_sub(amount),
+            None => {
+                // the first change of a user records the stake it had when the snapshots started,
+                // the stake maps are already updated here
+                let user_stake = self.staked_amount(storage, user_addr.clone())?;
+                let previous_stake = if is_stake { user_stake.saturating_sub(amount) } else { user_stake + amount };
+                if let Some(first_cycle) = self.first_snapshot_cycle(storage) {
+                    if !previous_stake.is_zero() {
+                        self.acc_stake_snapshots.save(storage, (user_addr.clone(), U64Key::from(first_cycle)), &previous_stake)?;
+                    }
+                }
+                user_stake
+            }
+        };
+        let total_stake = if is_stake { total_stake + amount } else { total_stake.saturating_sub(amount) };
+        self.acc_stake_snapshots.save(storage, (user_addr, U64Key::from(cycle)), &user_stake)?;
+        self.total_stake_snapshots.save(storage, U64Key::from(cycle), &total_stake)?;
+        Ok(())
+    }
+
+    // cycle the stake snapshots started at, the stake before the upgrade is recorded there
+    fn first_snapshot_cycle(&self, storage: &dyn Storage) -> Option<u64> {
+        self.total_stake_snapshots
+            .keys(storage, None, None, Order::Ascending)
+            .next()
+            .map(|key| u64::from_be_bytes(key.try_into().unwrap_or_default()))
+    }
+
    pub fn query_config(
         &self,
         deps: Deps,
@@ -794,6 +917,44 @@ impl<'a> DFCState<'a> {
         })
     }
     
+    pub fn query_stake_at(&self, deps: Deps, user_addr: String, cycle: u64) -> StdResult<GetStakeAtResponse> {
+        let address = deps.api.addr_validate(user_addr.as_str())?;
+        let has_snapshots = self.acc_stake_snapshots
+            .prefix(address.clone())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some();
+        // a user without snapshots hasn't changed its stake since the snapshots started,
+        // so it held its current stake from then on and nothing known before
+        if !has_snapshots {
+            let amount = match self.first_snapshot_cycle(deps.storage) {
+                Some(first_cycle) if cycle > first_cycle => self.staked_amount(deps.storage, address)?,
+                _ => Uint128::zero(),
+            };
+            return Ok(GetStakeAtResponse { amount });
+        }
+        let amount = self.acc_stake_snapshots
+            .prefix(address)
+            .range(deps.storage, None, Some(Bound::exclusive(U64Key::from(cycle))), Order::Descending)
+            .next()
+            .transpose()?
+            .map(|(_, stake)| stake)
+            .unwrap_or_default();
+
+        Ok(GetStakeAtResponse { amount })
+    }
+
+    pub fn query_total_stake_at(&self, deps: Deps, cycle: u64) -> StdResult<GetStakeAtResponse> {
+        let amount = self.total_stake_snapshots
+            .range(deps.storage, None, Some(Bound::exclusive(U64Key::from(cycle))), Order::Descending)
+            .next()
+            .transpose()?
+            .map(|(_, stake)| stake)
+            .unwrap_or_default();
+
+        Ok(GetStakeAtResponse { amount })
+    }
+
     pub fn query_current_cycle_rewards(&self, deps: Deps) -> StdResult<GetCurrentCycleRewards> {
         let base_state = self.base_state.load(deps.storage)?;
 
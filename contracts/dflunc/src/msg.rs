@@ -39,6 +39,14 @@ pub enum ExecuteMsg {
     SetDfcAddress {
         dfc_address: String,
     },
+    SetOwner {
+        owner: String,
+    },
+    UpdateConfig {
+        lunc_batch_amount: Option<Uint128>,
+        ustc_batch_amount: Option<Uint128>,
+        protocol_fees_reserved_rate: Option<u64>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -63,6 +71,14 @@ pub enum QueryMsg {
         user_address: String
     },
     GetCurrentCycleRewards {},
+    // stake held by the user when the cycle started
+    GetStakeAt {
+        user_address: String,
+        cycle: u64,
+    },
+    GetTotalStakeAt {
+        cycle: u64,
+    },
 }
 
 // We define a custom struct for each query response
@@ -137,5 +153,10 @@ pub struct GetUnclaimedFees {
     pub amount: Uint128,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetStakeAtResponse {
+    pub amount: Uint128,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MigrateMsg {}
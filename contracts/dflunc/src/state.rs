@@ -51,6 +51,10 @@ pub struct DFCState<'a> {
     pub acc_withdrawable_stake: Map<'a, Addr, Uint128>,
     pub acc_first_stake: Map<'a, Addr, u64>,
     pub acc_second_stake: Map<'a, Addr, u64>,
+
+    // staked dfc after every cycle in which it changed, used for voting power
+    pub acc_stake_snapshots: Map<'a, (Addr, U64Key), Uint128>,
+    pub total_stake_snapshots: Map<'a, U64Key, Uint128>,
 }
 
 impl Default for DFCState<'static> {
@@ -71,6 +75,8 @@ impl Default for DFCState<'static> {
             acc_withdrawable_stake: Map::new("ACC_WITHDRAWABLE_STAKE"),
             acc_first_stake: Map::new("ACC_FIRST_STAKE"),
             acc_second_stake: Map::new("ACC_SECOND_STAKE"),
+            acc_stake_snapshots: Map::new("ACC_STAKE_SNAPSHOTS"),
+            total_stake_snapshots: Map::new("TOTAL_STAKE_SNAPSHOTS"),
         }
     }
 }
@@ -0,0 +1,107 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_binary, Deps, DepsMut, Env, Timestamp, Uint128};
+use cw_storage_plus::U64Key;
+
+use crate::mock_querier::mock_dependencies;
+use crate::msg::{ExecuteMsg, GetStakeAtResponse, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::DFCState;
+
+const INITIAL_TIMESTAMP: u64 = 1_000_000;
+const PERIOD_DURATION: u64 = 86400;
+
+fn env_at_cycle(cycle: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(INITIAL_TIMESTAMP + cycle * PERIOD_DURATION);
+    env
+}
+
+fn instantiate(deps: DepsMut) {
+    let msg = InstantiateMsg {
+        dfc_address: "dfc".to_string(),
+        lunc_batch_amount: Uint128::from(1000u128),
+        ustc_batch_amount: Uint128::from(1000u128),
+        initial_timestamp: INITIAL_TIMESTAMP,
+        ustc_claimer_address: "claimer".to_string(),
+        protocol_fees_reserved_rate: 0,
+        burned_address: "burned".to_string(),
+        period_duration: PERIOD_DURATION,
+    };
+    DFCState::default().instantiate(deps, env_at_cycle(0), mock_info("owner", &[]), msg).unwrap();
+}
+
+fn stake(deps: DepsMut, cycle: u64, user: &str, amount: u128) {
+    let msg = ExecuteMsg::Stake { amount: Uint128::from(amount) };
+    DFCState::default().execute(deps, env_at_cycle(cycle), mock_info(user, &[]), msg).unwrap();
+}
+
+fn stake_at(deps: Deps, user: &str, cycle: u64) -> Uint128 {
+    let msg = QueryMsg::GetStakeAt { user_address: user.to_string(), cycle };
+    let res: GetStakeAtResponse = from_binary(&DFCState::default().query(deps, env_at_cycle(cycle), msg).unwrap()).unwrap();
+    res.amount
+}
+
+fn total_stake_at(deps: Deps, cycle: u64) -> Uint128 {
+    let msg = QueryMsg::GetTotalStakeAt { cycle };
+    let res: GetStakeAtResponse = from_binary(&DFCState::default().query(deps, env_at_cycle(cycle), msg).unwrap()).unwrap();
+    res.amount
+}
+
+#[test]
+fn stake_snapshots_count_from_the_next_cycle() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut());
+    stake(deps.as_mut(), 0, "alice", 100);
+    stake(deps.as_mut(), 0, "bob", 50);
+    stake(deps.as_mut(), 2, "alice", 30);
+
+    assert_eq!(stake_at(deps.as_ref(), "alice", 0), Uint128::zero());
+    assert_eq!(stake_at(deps.as_ref(), "alice", 1), Uint128::from(100u128));
+    assert_eq!(stake_at(deps.as_ref(), "alice", 2), Uint128::from(100u128));
+    assert_eq!(stake_at(deps.as_ref(), "alice", 3), Uint128::from(130u128));
+    assert_eq!(stake_at(deps.as_ref(), "bob", 3), Uint128::from(50u128));
+    assert_eq!(stake_at(deps.as_ref(), "carol", 3), Uint128::zero());
+    assert_eq!(total_stake_at(deps.as_ref(), 0), Uint128::zero());
+    assert_eq!(total_stake_at(deps.as_ref(), 1), Uint128::from(150u128));
+    assert_eq!(total_stake_at(deps.as_ref(), 3), Uint128::from(180u128));
+
+    let msg = ExecuteMsg::Unstake { amount: Uint128::from(40u128) };
+    DFCState::default().execute(deps.as_mut(), env_at_cycle(4), mock_info("alice", &[]), msg).unwrap();
+    assert_eq!(stake_at(deps.as_ref(), "alice", 4), Uint128::from(130u128));
+    assert_eq!(stake_at(deps.as_ref(), "alice", 5), Uint128::from(90u128));
+    assert_eq!(total_stake_at(deps.as_ref(), 5), Uint128::from(140u128));
+}
+
+#[test]
+fn stakes_made_before_the_upgrade_count_from_the_migration_cycle() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut());
+    stake(deps.as_mut(), 0, "alice", 100);
+    stake(deps.as_mut(), 0, "bob", 50);
+    stake(deps.as_mut(), 3, "carol", 25);
+
+    // contracts deployed before the snapshots have none of them
+    let state = DFCState::default();
+    for (user, cycle) in [("alice", 0), ("bob", 0), ("carol", 3)] {
+        state.acc_stake_snapshots.remove(&mut deps.storage, (cosmwasm_std::Addr::unchecked(user), U64Key::from(cycle)));
+    }
+    state.total_stake_snapshots.remove(&mut deps.storage, U64Key::from(0));
+    state.total_stake_snapshots.remove(&mut deps.storage, U64Key::from(3));
+
+    deps.querier.with_token_balance(175);
+    state.migrate(deps.as_mut(), env_at_cycle(3), MigrateMsg {}).unwrap();
+
+    // nothing is known before the migration cycle
+    assert_eq!(stake_at(deps.as_ref(), "alice", 3), Uint128::zero());
+    assert_eq!(stake_at(deps.as_ref(), "alice", 4), Uint128::from(100u128));
+    assert_eq!(stake_at(deps.as_ref(), "alice", 9), Uint128::from(100u128));
+    assert_eq!(total_stake_at(deps.as_ref(), 3), Uint128::zero());
+    assert_eq!(total_stake_at(deps.as_ref(), 4), Uint128::from(175u128));
+
+    // the first change keeps the stake held at the migration
+    stake(deps.as_mut(), 5, "bob", 10);
+    assert_eq!(stake_at(deps.as_ref(), "bob", 3), Uint128::zero());
+    assert_eq!(stake_at(deps.as_ref(), "bob", 4), Uint128::from(50u128));
+    assert_eq!(stake_at(deps.as_ref(), "bob", 5), Uint128::from(50u128));
+    assert_eq!(stake_at(deps.as_ref(), "bob", 6), Uint128::from(60u128));
+    assert_eq!(total_stake_at(deps.as_ref(), 6), Uint128::from(185u128));
+}
@@ -1,7 +1,6 @@
-#[cfg(not(feature = "library"))]
 use crate::error::ContractError;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult
 };
 
 use cw2::set_contract_version;
@@ -21,31 +20,59 @@ impl<'a> FomoDFCState<'a> {
         _env: Env,
         info: MessageInfo,
         msg: InstantiateMsg,
-    ) -> StdResult<Response> {
+    ) -> Result<Response, ContractError> {
+        if msg.initial_lunc_amount_in.is_zero() || msg.fire_dfc_amount.is_zero() {
+            return Err(ContractError::NotValidFireCost {});
+        }
+        self.validate_price_step(&msg.price_step)?;
+        let keeper_tip_percent = msg.keeper_tip_percent.unwrap_or(0);
+        self.validate_keeper_tip(keeper_tip_percent)?;
+        let jackpot_carry_percent = msg.jackpot_carry_percent.unwrap_or(0);
+        self.validate_jackpot_carry(jackpot_carry_percent)?;
+        let timer_policy = msg.timer_policy.unwrap_or_default();
+        self.validate_timer_policy(&timer_policy)?;
+        self.validate_claim_window(msg.claim_window)?;
+        self.validate_prize_table(&msg.prize_table)?;
+
         set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
         let dfc_address = deps.api.addr_canonicalize(msg.dfc_address.as_str())?;
         let dflunc_address = deps.api.addr_canonicalize(msg.dflunc_address.as_str())?;
         let dev_address = deps.api.addr_canonicalize(msg.dev_address.as_str())?;
         let burned_address = deps.api.addr_canonicalize(msg.burned_address.as_str())?;
-        CONFIG.save(
-            deps.storage,
-            &Config {
-                dfc_address,
-                dflunc_address,
-                dev_address,
-                burned_address,
-                max_delay_time: msg.max_delay_time,
-                delay_time_per_burn: msg.delay_time_per_burn,
-                initial_lunc_amount_in: msg.initial_lunc_amount_in,
-                dividend_percent: msg.dividend_percent,
-                burned_percent: msg.burned_percent,
-                invite_percent: msg.invite_percent,
-                dev_percent: msg.dev_percent,
-                ustc_last_fire_numerator: msg.ustc_last_fire_numerator,
-                ustc_last_fire_denominator: msg.ustc_last_fire_denominator,
-            },
-        )?;
+        let governance_address = match msg.governance_address {
+            Some(governance_address) => Some(deps.api.addr_canonicalize(governance_address.as_str())?),
+            None => None,
+        };
+        let config = Config {
+            dfc_address,
+            dflunc_address,
+            dev_address,
+            burned_address,
+            max_delay_time: msg.max_delay_time,
+            delay_time_per_burn: msg.delay_time_per_burn,
+            initial_lunc_amount_in: msg.initial_lunc_amount_in,
+            fire_dfc_amount: msg.fire_dfc_amount,
+            price_step: msg.price_step,
+            dividend_percent: msg.dividend_percent,
+            burned_percent: msg.burned_percent,
+            invite_percent: msg.invite_percent,
+            second_invite_percent: msg.second_invite_percent.unwrap_or(0),
+            dev_percent: msg.dev_percent,
+            unused_invite_destination: msg.unused_invite_destination,
+            prize_table: msg.prize_table,
+            ustc_last_fire_numerator: None,
+            ustc_last_fire_denominator: None,
+            keeper_tip_percent,
+            jackpot_carry_percent,
+            timer_policy,
+            claim_window: msg.claim_window,
+            governance_address,
+        };
+        self.validate_split(&config)?;
+        CONFIG.save(deps.storage, &config)?;
+        self.owner.save(deps.storage, &info.sender)?;
         self.lunc_amount_in_required.save(deps.storage, U64Key::from(0), &msg.initial_lunc_amount_in)?;
+        self.claims_tracked_from.save(deps.storage, &0)?;
         Ok(Response::new()
             .add_attribute("method", "instantiate")
             .add_attribute("owner", info.sender))
@@ -59,21 +86,77 @@ impl<'a> FomoDFCState<'a> {
         msg: ExecuteMsg,
     ) -> Result<Response, ContractError> {
         match msg {
-            ExecuteMsg::Burn { invite_address } => self.burn(deps, env, info, invite_address),
+            ExecuteMsg::Burn { invite_address, fires, team } => self.burn(deps, env, info, invite_address, fires, team),
+            ExecuteMsg::Receive(msg) => self.receive(deps, env, info, msg),
+            ExecuteMsg::DepositLunc {} => self.deposit_lunc(deps, env, info),
+            ExecuteMsg::WithdrawLuncDeposit {} => self.withdraw_lunc_deposit(deps, env, info),
             ExecuteMsg::ClaimLuncDividend { cycle } => self.claim_lunc_dividend(deps, env, info, cycle),
             ExecuteMsg::ClaimUstcDividend { cycle } => self.claim_ustc_dividend(deps, env, info, cycle),
+            ExecuteMsg::ClaimAllDividends { cycles, limit } => self.claim_all_dividends(deps, env, info, cycles, limit),
+            ExecuteMsg::WithdrawReferralRewards {} => self.withdraw_referral_rewards(deps, env, info),
+            ExecuteMsg::WithdrawDevRewards {} => self.withdraw_dev_rewards(deps, env, info),
+            ExecuteMsg::TransferOwnership { owner } => self.transfer_ownership(deps, env, info, owner),
+            ExecuteMsg::AcceptOwnership {} => self.accept_ownership(deps, env, info),
+            ExecuteMsg::SettleRound {} => self.settle_round(deps, env, info),
+            ExecuteMsg::UpdateConfig(msg) => self.update_config(deps, env, info, *msg),
+            ExecuteMsg::SetTeam { team_id, team } => self.set_team(deps, env, info, team_id, team),
+            ExecuteMsg::RemoveTeam { team_id } => self.remove_team(deps, env, info, team_id),
+            ExecuteMsg::SweepExpired { cycle } => self.sweep_expired(deps, env, info, cycle),
         }
     }
     
-    pub fn query(&self, deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         match msg {
             QueryMsg::GetConfig {  } => to_binary(&self.query_config(deps)?),
+            QueryMsg::GetPendingConfig {} => to_binary(&self.query_pending_config(deps)?),
+            QueryMsg::GetOwner {} => to_binary(&self.query_owner(deps)?),
             QueryMsg::GetCycleInfo { cycle } => to_binary(&self.query_cycle_info(deps, cycle)?),
             QueryMsg::GetUserInfo { user_address, cycle } => to_binary(&self.query_user_info(deps, user_address, cycle)?),
+            QueryMsg::GetCyclePrizes { cycle } => to_binary(&self.query_cycle_prizes(deps, cycle)?),
+            QueryMsg::GetReferralInfo { user_address } => to_binary(&self.query_referral_info(deps, user_address)?),
+            QueryMsg::GetDividendCarry {} => to_binary(&self.query_dividend_carry(deps)?),
+            QueryMsg::GetLuncDeposit { user_address } => to_binary(&self.query_lunc_deposit(deps, user_address)?),
+            QueryMsg::GetPendingDevRewards {} => to_binary(&self.query_pending_dev_rewards(deps)?),
+            QueryMsg::GetInvitees { user_address, start_after, limit } => to_binary(&self.query_invitees(deps, user_address, start_after, limit)?),
+            QueryMsg::GetClaimable { user_address, start_after, limit } => to_binary(&self.query_claimable(deps, user_address, start_after, limit)?),
+            QueryMsg::GetRoundStatus {} => to_binary(&self.query_round_status(deps, env)?),
+            QueryMsg::GetJackpot {} => to_binary(&self.query_jackpot(deps)?),
+            QueryMsg::GetTeams { cycle } => to_binary(&self.query_teams(deps, cycle)?),
+            QueryMsg::GetPlayerStats { address } => to_binary(&self.query_player_stats(deps, address)?),
+            QueryMsg::GetTopPlayers { denom, start_after, limit } => to_binary(&self.query_top_players(deps, denom, start_after, limit)?),
+            QueryMsg::GetRounds { start_after, limit } => to_binary(&self.query_rounds(deps, start_after, limit)?),
+            QueryMsg::GetRoundParticipants { cycle, start_after, limit } => to_binary(&self.query_round_participants(deps, cycle, start_after, limit)?),
+            QueryMsg::GetPriceCurve { count } => to_binary(&self.query_price_curve(deps, env, count)?),
         }
     }
     
-    pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    pub fn migrate(&self, deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response> {
+        if self.owner.may_load(deps.storage)?.is_none() {
+            let owner = match msg.owner {
+                Some(owner) => deps.api.addr_validate(&owner)?,
+                None => return Err(StdError::generic_err("An owner is required for a contract without owner")),
+            };
+            self.owner.save(deps.storage, &owner)?;
+        }
+        // the last burner keeps the share it had before the prize table
+        let mut config = CONFIG.load(deps.storage)?;
+        if let Some(prize_table) = config.legacy_prize_table() {
+            config.prize_table = prize_table;
+            config.ustc_last_fire_numerator = None;
+            config.ustc_last_fire_denominator = None;
+            CONFIG.save(deps.storage, &config)?;
+        }
+        // claims of the cycles up to the current one weren't counted, so those cycles can't be swept
+        if self.claims_tracked_from.may_load(deps.storage)?.is_none() {
+            let current_cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
+            self.claims_tracked_from.save(deps.storage, &(current_cycle + 1))?;
+        }
+        // the start of a round running across the upgrade wasn't recorded, its max round length counts from now
+        let current_cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
+        let total_fires = self.cycle_total_fires.may_load(deps.storage, U64Key::from(current_cycle))?.unwrap_or(0);
+        if total_fires > 0 && self.cycle_start_time.may_load(deps.storage, U64Key::from(current_cycle))?.is_none() {
+            self.cycle_start_time.save(deps.storage, U64Key::from(current_cycle), &env.block.time.seconds())?;
+        }
         Ok(Response::default())
     }
 }
\ No newline at end of file
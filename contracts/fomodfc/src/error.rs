@@ -16,6 +16,17 @@ pub enum ContractError {
         cycle: u64,
     },
 
+    #[error("DividendExpired: Dividends of cycle {cycle} could be claimed for {claim_window} rounds.")]
+    DividendExpired {
+        cycle: u64,
+        claim_window: u64,
+    },
+
+    #[error("NotSweepable: Dividends of cycle {cycle} are still claimable or were claimed before claims were tracked.")]
+    NotSweepable {
+        cycle: u64,
+    },
+
     #[error("AlreadyWithdrawed: {cycle}.")]
     AlreadyWithdrawed {
         cycle: u64,
@@ -29,6 +40,9 @@ pub enum ContractError {
     #[error("NotOwner: Sender is {sender}, but owner is {owner}.")]
     NotOwner { sender: String, owner: String },
 
+    #[error("NotPendingOwner: Sender {sender} is not the pending owner.")]
+    NotPendingOwner { sender: String },
+
     #[error("NotClaimer: Sender is {sender}, but claimer is {claimer}.")]
     NotClaimer { sender: String, claimer: String },
 
@@ -39,6 +53,60 @@ pub enum ContractError {
     NotValidBatchNumber {
     },
 
+    #[error("Initial lunc amount and fire dfc amount should be greater than zero.")]
+    NotValidFireCost {
+    },
+
+    #[error("Linear price increment should be greater than zero, exponential rate should be in [1, 100000].")]
+    NotValidPriceStep {
+    },
+
+    #[error("NotValidSplit: Dividend, burned, invite, second invite and dev percents sum to {total}, should be 100000.")]
+    NotValidSplit { total: Uint128 },
+
+    #[error("Keeper tip percent should be in [0, 100000].")]
+    NotValidKeeperTip {
+    },
+
+    #[error("NotValidClaimWindow: The claim window should be at least 1 round and can't be set and cleared at once.")]
+    NotValidClaimWindow {
+    },
+
+    #[error("NotValidTimerPolicy: Steps and the max round length should be above 0, percents at most 100000.")]
+    NotValidTimerPolicy {
+    },
+
+    #[error("Jackpot carry percent should be in [0, 100000].")]
+    NotValidJackpotCarry {
+    },
+
+    #[error("Prize percents should be descending for previous burners, at most 10 of them, and sum to at most 100000.")]
+    NotValidPrizeTable {
+    },
+
+    #[error("NotValidFires: A burn should buy between 1 and {max} fires.")]
+    NotValidFires { max: u64 },
+
+    #[error("NotDfcToken: Sender is {sender}, only dfc can be received.")]
+    NotDfcToken { sender: String },
+
+    #[error("NotValidFireFee: Received {amount}, should be a multiple of {fire_dfc_amount}.")]
+    NotValidFireFee { amount: Uint128, fire_dfc_amount: Uint128 },
+
+    #[error("NotValidTeam: Team percents should sum to 100000 and the ustc share be at most 100000.")]
+    NotValidTeam {
+    },
+
+    #[error("TeamNotFound: There is no team {team_id}.")]
+    TeamNotFound { team_id: u64 },
+
+    #[error("Can't invite yourself.")]
+    SelfInvite {
+    },
+
+    #[error("RoundNotEnded: The round ends at {end_time}.")]
+    RoundNotEnded { end_time: u64 },
+
     #[error("No reward.")]
     NoRewards {
     },
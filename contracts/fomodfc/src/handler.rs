@@ -1,17 +1,49 @@
 use crate::error::ContractError;
-use crate::state::{FomoDFCState, CONFIG, LUNC_DENOM, USTC_DENOM};
-use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Uint128, Coin, StdResult, 
-    CosmosMsg, WasmMsg, to_binary, Addr, Storage, QuerierWrapper, Api, WasmQuery, QueryRequest};
-use cw_storage_plus::U64Key;
+use crate::state::{Config, FomoDFCState, Prize, CONFIG, PENDING_CONFIG, LUNC_DENOM, USTC_DENOM};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Uint128, Coin, StdResult, StdError, Order,
+    CosmosMsg, WasmMsg, to_binary, from_binary, Addr, Storage, QuerierWrapper, Api, WasmQuery, QueryRequest};
+use cw_storage_plus::{Bound, PrimaryKey, U128Key, U64Key};
+use std::convert::TryInto;
 use terraswap::asset::{Asset, AssetInfo};
 use crate::msg::{
-    GetConfigResponse, GetCycleInfoResponse, GetUserInfoResponse
+    ClaimableCycle, GetClaimableResponse, GetConfigResponse, GetCycleInfoResponse, GetCyclePrizesResponse, GetDividendCarryResponse,
+    GetInviteesResponse, GetLuncDepositResponse, GetOwnerResponse, GetPendingConfigResponse, GetPendingDevRewardsResponse, GetReferralInfoResponse, PrizeInfo, PrizeTable, PrizeTier, ReceiveMsg, GetUserInfoResponse, GetPriceCurveResponse, GetRoundParticipantsResponse, GetRoundStatusResponse, GetRoundsResponse,
+    GetJackpotResponse, DividendDenom, GetPlayerStatsResponse, GetTeamsResponse, GetTopPlayersResponse, PriceStep, RoundParticipant, RoundSummary, Team, TeamInfo, TimerPolicy, TopPlayer, UnusedInviteDestination, UpdateConfigMsg
 };
 use dflunc::msg::{ExecuteMsg as DfluncExecuteMsg, QueryMsg, GetBaseStateResponse};
-use cw20::Cw20ExecuteMsg;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
 
 const MAX_BPS: u64 = 100000;
+const MAX_PRICE_CURVE_LENGTH: u32 = 100;
+const MAX_PREVIOUS_BURNERS: usize = 10;
+const MAX_FIRES_PER_BURN: u64 = 100;
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+// how the lunc paid for fires is shared out
+#[derive(Default)]
+struct LuncSplit {
+    lunc_amount_in: Uint128,
+    dividend: Uint128,
+    burned: Uint128,
+    invite: Uint128,
+    second_invite: Uint128,
+    dev: Uint128,
+    pot: Uint128,
+}
+
+impl LuncSplit {
+    fn add(&mut self, other: &LuncSplit) {
+        self.lunc_amount_in += other.lunc_amount_in;
+        self.dividend += other.dividend;
+        self.burned += other.burned;
+        self.invite += other.invite;
+        self.second_invite += other.second_invite;
+        self.dev += other.dev;
+        self.pot += other.pot;
+    }
+}
 
 impl<'a> FomoDFCState<'a> {
     pub fn burn(
@@ -20,9 +52,122 @@ impl<'a> FomoDFCState<'a> {
         env: Env,
         info: MessageInfo,
         invite_address: Option<String>,
+        fires: u64,
+        team: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        self.burn_fires(deps, env, info, invite_address, fires, team, false)
+    }
+
+    // the dfc fee of a cw20 send has been received already, its lunc comes from the sender's deposit
+    pub fn receive(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Cw20ReceiveMsg,
     ) -> Result<Response, ContractError> {
-    
         let config = CONFIG.load(deps.storage)?;
+        if deps.api.addr_canonicalize(info.sender.as_str())? != config.dfc_address {
+            return Err(ContractError::NotDfcToken { sender: info.sender.to_string() });
+        }
+
+        match from_binary(&msg.msg)? {
+            ReceiveMsg::Burn { invite_address, team } => {
+                let fires = msg.amount.u128() / config.fire_dfc_amount.u128();
+                if fires == 0 || config.fire_dfc_amount * Uint128::from(fires) != msg.amount {
+                    return Err(ContractError::NotValidFireFee {
+                        amount: msg.amount,
+                        fire_dfc_amount: config.fire_dfc_amount,
+                    });
+                }
+                if fires > MAX_FIRES_PER_BURN as u128 {
+                    return Err(ContractError::NotValidFires { max: MAX_FIRES_PER_BURN });
+                }
+
+                let burner = deps.api.addr_validate(&msg.sender)?;
+                let lunc_deposit = self.user_lunc_deposit.may_load(deps.storage, burner.clone())?.unwrap_or(Uint128::zero());
+                self.user_lunc_deposit.remove(deps.storage, burner.clone());
+                let burner_info = MessageInfo {
+                    sender: burner,
+                    funds: vec![Coin {
+                        denom: LUNC_DENOM.to_string(),
+                        amount: lunc_deposit,
+                    }],
+                };
+                self.burn_fires(deps, env, burner_info, invite_address, fires as u64, team, true)
+            }
+        }
+    }
+
+    // lunc for a burn through a cw20 send, deposited by an earlier message of the same transaction
+    pub fn deposit_lunc(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let amount = info
+            .funds
+            .iter()
+            .find(|fund| fund.denom == LUNC_DENOM)
+            .map(|fund| fund.amount)
+            .unwrap_or_default();
+        if amount.is_zero() {
+            return Err(ContractError::AmountIsZero {});
+        }
+        let deposit = self.user_lunc_deposit.update(deps.storage, info.sender.clone(), |deposit| -> StdResult<_> {
+            Ok(deposit.unwrap_or(Uint128::zero()) + amount)
+        })?;
+
+        let res = Response::new()
+            .add_attribute("action", "deposit_lunc")
+            .add_attribute("owner", info.sender)
+            .add_attribute("amount", amount.to_string())
+            .add_attribute("deposit", deposit.to_string());
+
+        Ok(res)
+    }
+
+    pub fn withdraw_lunc_deposit(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let amount = self.user_lunc_deposit.may_load(deps.storage, info.sender.clone())?.unwrap_or(Uint128::zero());
+        if amount.is_zero() {
+            return Err(ContractError::AmountIsZero {});
+        }
+        self.user_lunc_deposit.remove(deps.storage, info.sender.clone());
+
+        let res = Response::new()
+            .add_message(self.send_lunc(&deps.querier, info.sender.clone(), amount)?)
+            .add_attribute("action", "withdraw_lunc_deposit")
+            .add_attribute("owner", info.sender)
+            .add_attribute("amount", amount.to_string());
+
+        Ok(res)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn burn_fires(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        invite_address: Option<String>,
+        fires: u64,
+        team_id: Option<u64>,
+        dfc_received: bool,
+    ) -> Result<Response, ContractError> {
+        if fires == 0 || fires > MAX_FIRES_PER_BURN {
+            return Err(ContractError::NotValidFires { max: MAX_FIRES_PER_BURN });
+        }
+        let team = match team_id {
+            Some(team_id) => Some(self.teams.may_load(deps.storage, U64Key::from(team_id))?.ok_or(ContractError::TeamNotFound { team_id })?),
+            None => None,
+        };
+        let mut config = CONFIG.load(deps.storage)?;
 
         let mut current_cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
         let mut end_time = self.end_time.may_load(deps.storage)?.unwrap_or(0);
@@ -31,129 +176,172 @@ impl<'a> FomoDFCState<'a> {
         let mut messages: Vec<CosmosMsg> = vec![];
         let burn_addr = deps.api.addr_humanize(&config.burned_address)?;
 
+        let dfc_amount = config.fire_dfc_amount.checked_mul(Uint128::from(fires)).map_err(StdError::from)?;
         let burn_dfc_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: deps.api.addr_humanize(&config.dfc_address)?.to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
-                owner: info.sender.to_string(),
-                recipient: burn_addr.to_string(),
-                amount: Uint128::from(1000000u128),
-            })?,
+            msg: if dfc_received {
+                to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: burn_addr.to_string(),
+                    amount: dfc_amount,
+                })?
+            } else {
+                to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: info.sender.to_string(),
+                    recipient: burn_addr.to_string(),
+                    amount: dfc_amount,
+                })?
+            },
             funds: vec![],
         });
         messages.push(burn_dfc_msg);
 
+        let mut in_final_window = false;
         if current_time > end_time {
             // it means the first cycle will start
-            if total_fires > 0 {                  
-                let dfc_base_state: GetBaseStateResponse =
-                    deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-                        contract_addr: deps.api.addr_humanize(&config.dflunc_address)?.to_string(),
-                        msg: to_binary(&QueryMsg::GetBaseState {})?,
-                    }))?;
-                let ustc_claimed_amount = dfc_base_state.total_protocol_fees_reserved - dfc_base_state.withdrawed_protocol_fees_reserved;
-                if ustc_claimed_amount > Uint128::zero() {
-                    let claim_ustc_from_dflunc_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-                            contract_addr: deps.api.addr_humanize(&config.dflunc_address)?.to_string(),
-                            msg: to_binary(&DfluncExecuteMsg::ClaimUstcReservedFees {
-                        })?,
-                        funds: vec![],
-                    });
-                    messages.push(claim_ustc_from_dflunc_msg);  
-    
-                    let messages_ustc_rewards = self.distribute_ustc(
-                        &deps.querier,
-                        deps.storage,
-                        env.clone(),
-                        ustc_claimed_amount,
-                        current_cycle,
-                        total_fires,
-                        config.ustc_last_fire_numerator,
-                        config.ustc_last_fire_denominator,
-                    )?;
-                    messages.extend(messages_ustc_rewards);
-                }
+            if total_fires > 0 {
+                messages.extend(self.settle_cycle(
+                    deps.api,
+                    &deps.querier,
+                    deps.storage,
+                    env.clone(),
+                    &config,
+                    current_cycle,
+                    total_fires,
+                    None,
+                )?);
                 total_fires = 0;
                 current_cycle += 1;
-                self.current_cycle.save(deps.storage, &current_cycle)?;
-                self.lunc_amount_in_required.save(deps.storage, U64Key::from(current_cycle), &config.initial_lunc_amount_in)?;
-                self.cycle_total_fires.save(deps.storage, U64Key::from(current_cycle), &0)?;
+                config = CONFIG.load(deps.storage)?;
             }
 
-            self.end_time.save(deps.storage, &(current_time + config.max_delay_time))?;
+            self.cycle_start_time.save(deps.storage, U64Key::from(current_cycle), &current_time)?;
+            end_time = current_time.saturating_add(config.max_delay_time);
         } else {
-            end_time += config.delay_time_per_burn;
-            if end_time > current_time + config.max_delay_time {
-                end_time = current_time + config.max_delay_time;
-            }
-            self.end_time.save(deps.storage, &end_time)?;
+            in_final_window = end_time - current_time <= config.timer_policy.final_seconds;
+            let delay_time_per_fire = self.delay_time_per_fire(deps.as_ref(), &config, current_cycle, current_time)?;
+            end_time = end_time.saturating_add(delay_time_per_fire.saturating_mul(fires));
+            end_time = end_time.min(current_time.saturating_add(config.max_delay_time));
         }
+        if let Some(max_round_length) = config.timer_policy.max_round_length {
+            let start_time = self.cycle_start_time.may_load(deps.storage, U64Key::from(current_cycle))?.unwrap_or(current_time);
+            end_time = end_time.min(start_time.saturating_add(max_round_length));
+        }
+        self.end_time.save(deps.storage, &end_time)?;
 
+        // every fire costs the stepped price after the one before it
         let mut lunc_amount_in_required = self.lunc_amount_in_required.load(deps.storage, U64Key::from(current_cycle))?;
-        
-        let overpayment = self.validate_lunc_func(info.clone(), lunc_amount_in_required)?;
+        let mut fire_prices = vec![];
+        for _ in 0..fires {
+            fire_prices.push(lunc_amount_in_required);
+            lunc_amount_in_required = self.next_fire_price(lunc_amount_in_required, &config, in_final_window)?;
+        }
+        let total_lunc_amount_in: Uint128 = fire_prices.iter().sum();
+
+        let overpayment = self.validate_lunc_func(info.clone(), total_lunc_amount_in)?;
+        // kept as a deposit so the burn sends lunc at most once, the burner withdraws it later
         if overpayment > Uint128::zero() {
-            messages.push(self.send_lunc(&deps.querier, info.sender.clone(), overpayment)?);
+            self.user_lunc_deposit.update(deps.storage, info.sender.clone(), |deposit| -> StdResult<_> {
+                Ok(deposit.unwrap_or(Uint128::zero()) + overpayment)
+            })?;
         }
 
-        let inviter_rewards = self.distribute_rewards_to_inviter(
-            deps.api,
-            &deps.querier,
-            deps.storage,
-            &mut messages,
-            lunc_amount_in_required,
-            invite_address,
-            config.invite_percent,
-        )?;
+        let inviter = self.resolve_inviter(deps.api, deps.storage, &info.sender, invite_address)?;
+        let second_inviter = match &inviter {
+            Some(inviter) => self.user_invited_address.may_load(deps.storage, inviter.clone())?,
+            None => None,
+        };
 
-        let lunc_burned = self.burn_lunc(
-            deps.as_ref(),
-            &mut messages,
-            lunc_amount_in_required,
-            burn_addr.clone(),
-            config.burned_percent,
-        )?;
+        // fires are sold one after another, so the dividend of a fire also goes to the earlier fires of this burn
+        let mut split = LuncSplit::default();
+        for fire_price in fire_prices {
+            let fire_split = self.split_fire(fire_price, &config, team.as_ref(), inviter.is_some(), second_inviter.is_some());
+            if total_fires == 0 {
+                // nobody holds a fire yet to take the first dividend, so it seeds the dividend of the next fire
+                let lunc_dividend_carry = self.lunc_dividend_carry.may_load(deps.storage)?.unwrap_or(Uint128::zero());
+                self.lunc_dividend_carry.save(deps.storage, &(lunc_dividend_carry + fire_split.dividend))?;
+                self.user_cycle_fires.save(deps.storage, (info.sender.clone(), U64Key::from(current_cycle)), &1)?;
+            } else {
+                self.calculate_dividend(
+                    deps.storage, 
+                    fire_split.dividend, 
+                    info.sender.clone(), 
+                    total_fires, 
+                    current_cycle)?;
+            }
+            split.add(&fire_split);
+            total_fires += 1;
+        }
+
+        // referral and dev shares are accrued and withdrawn later, so the burn share is the only payout sent
+        for (referrer, amount) in [(&inviter, split.invite), (&second_inviter, split.second_invite)] {
+            if let Some(referrer) = referrer {
+                if !amount.is_zero() {
+                    self.pending_referral_rewards.update(deps.storage, referrer.clone(), |rewards| -> StdResult<_> {
+                        Ok(rewards.unwrap_or(Uint128::zero()) + amount)
+                    })?;
+                    self.user_referral_earnings.update(deps.storage, referrer.clone(), |earnings| -> StdResult<_> {
+                        Ok(earnings.unwrap_or(Uint128::zero()) + amount)
+                    })?;
+                }
+            }
+        }
+        if !split.dev.is_zero() {
+            let pending_dev_rewards = self.pending_dev_rewards.may_load(deps.storage)?.unwrap_or(Uint128::zero());
+            self.pending_dev_rewards.save(deps.storage, &(pending_dev_rewards + split.dev))?;
+        }
+        if !split.burned.is_zero() {
+            messages.push(self.send_lunc(&deps.querier, burn_addr.clone(), split.burned)?);
+        }
 
         self.cycle_total_burned.update(deps.storage, U64Key::from(current_cycle), |burned_lunc| -> StdResult<_> {
-            Ok(burned_lunc.unwrap_or(Uint128::zero()) + lunc_burned)
+            Ok(burned_lunc.unwrap_or(Uint128::zero()) + split.burned)
         })?;
-
-        let dev_rewards = self.distribute_dev_rewards(
-            deps.as_ref(),
-            &mut messages,
-            lunc_amount_in_required,
-            deps.api.addr_humanize(&config.dev_address)?,
-            config.dev_percent,
-        )?;
-
-        let left_lunc_to_dividend = lunc_amount_in_required - inviter_rewards - lunc_burned - dev_rewards;
-        if total_fires == 0 {
-            messages.push(self.send_lunc(&deps.querier, burn_addr.clone(), left_lunc_to_dividend)?);
-
-            self.user_cycle_fires.save(deps.storage, (info.sender.clone(), U64Key::from(current_cycle)), &1)?;
-            self.cycle_total_burned.update(deps.storage, U64Key::from(current_cycle), |burned_lunc| -> StdResult<_> {
-                Ok(burned_lunc.unwrap_or(Uint128::zero()) + left_lunc_to_dividend)
+        if !split.pot.is_zero() {
+            self.cycle_lunc_pot.update(deps.storage, U64Key::from(current_cycle), |pot| -> StdResult<_> {
+                Ok(pot.unwrap_or(Uint128::zero()) + split.pot)
             })?;
-        } else {
-            self.calculate_dividend(
-                deps.storage, 
-                left_lunc_to_dividend, 
-                info.sender.clone(), 
-                total_fires, 
-                current_cycle)?;
         }
-
-        lunc_amount_in_required =  lunc_amount_in_required + Uint128::from(100000000u128);
+        // the team of the last burn is the one that wins the round
+        match team_id {
+            Some(team_id) => {
+                self.cycle_team_fires.update(deps.storage, (U64Key::from(current_cycle), U64Key::from(team_id)), |team_fires| -> StdResult<_> {
+                    Ok(team_fires.unwrap_or(0) + fires)
+                })?;
+                self.user_cycle_team_fires.update(deps.storage, (info.sender.clone(), U64Key::from(current_cycle), U64Key::from(team_id)), |team_fires| -> StdResult<_> {
+                    Ok(team_fires.unwrap_or(0) + fires)
+                })?;
+                self.cycle_last_team.save(deps.storage, U64Key::from(current_cycle), &team_id)?;
+            }
+            None => self.cycle_last_team.remove(deps.storage, U64Key::from(current_cycle)),
+        }
         self.lunc_amount_in_required.save(deps.storage, U64Key::from(current_cycle), &lunc_amount_in_required)?;
-        self.cycle_total_fires.update(deps.storage, U64Key::from(current_cycle), |fire| -> StdResult<_> {
-            Ok(fire.unwrap_or(0) + 1)
-        })?;
+        self.cycle_total_fires.save(deps.storage, U64Key::from(current_cycle), &total_fires)?;
+        let user_cycle_fires = self.user_cycle_fires.load(deps.storage, (info.sender.clone(), U64Key::from(current_cycle)))?;
+        self.cycle_user_fires.save(deps.storage, (U64Key::from(current_cycle), info.sender.clone()), &user_cycle_fires)?;
         self.cycle_last_burner.save(deps.storage, U64Key::from(current_cycle), &info.sender.clone())?;
+        self.update_cycle_winners(deps.storage, &info.sender, current_cycle)?;
         self.user_burned_at_least_once.save(deps.storage, info.sender.clone(), &true)?;
+        let mut stats = self.player_stats.may_load(deps.storage, info.sender.clone())?.unwrap_or_default();
+        stats.total_fires += fires;
+        stats.lunc_spent += total_lunc_amount_in;
+        self.player_stats.save(deps.storage, info.sender.clone(), &stats)?;
                    
         let res = Response::new()
             .add_messages(messages)
             .add_attribute("action", "burn")
-            .add_attribute("burner", info.sender);
+            .add_attribute("burner", info.sender)
+            .add_attribute("fires", fires.to_string())
+            .add_attribute("lunc_amount_in", split.lunc_amount_in.to_string())
+            .add_attribute("lunc_deposit_amount", overpayment.to_string())
+            .add_attribute("dividend_amount", split.dividend.to_string())
+            .add_attribute("burned_amount", split.burned.to_string())
+            .add_attribute("invite_amount", split.invite.to_string())
+            .add_attribute("second_invite_amount", split.second_invite.to_string())
+            .add_attribute("dev_amount", split.dev.to_string())
+            .add_attribute("pot_amount", split.pot.to_string())
+            .add_attribute("team", team_id.map(|team_id| team_id.to_string()).unwrap_or_default())
+            .add_attribute("inviter", inviter.map(|inviter| inviter.to_string()).unwrap_or_default())
+            .add_attribute("second_inviter", second_inviter.map(|inviter| inviter.to_string()).unwrap_or_default());
     
         Ok(res)
     }
@@ -165,6 +353,7 @@ impl<'a> FomoDFCState<'a> {
         info: MessageInfo,
         cycle: u64,
     ) -> Result<Response, ContractError> {
+        self.check_not_expired(deps.as_ref(), cycle)?;
         let cycle_avg_lunc_dividend = self.cycle_avg_lunc_dividend.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero());
         let user_cycle_fires = self.user_cycle_fires.may_load(deps.storage, (info.sender.clone(), U64Key::from(cycle)))?.unwrap_or(0);
         let user_cycle_dividend_withdrawed = self.user_cycle_dividend_withdrawed.may_load(deps.storage, (info.sender.clone(), U64Key::from(cycle)))?.unwrap_or(Uint128::zero());
@@ -178,6 +367,8 @@ impl<'a> FomoDFCState<'a> {
         self.user_cycle_dividend_withdrawed.update(deps.storage, (info.sender.clone(), U64Key::from(cycle)), |dividend| -> StdResult<_> {
             Ok(dividend.unwrap_or(Uint128::zero()) + user_left_lunc_dividend)
         })?;
+        self.record_dividends_claimed(deps.storage, &info.sender, user_left_lunc_dividend, Uint128::zero())?;
+        self.record_cycle_claim(deps.storage, cycle, user_left_lunc_dividend, Uint128::zero())?;
         let res = Response::new()
             .add_messages(messages)
             .add_attribute("action", "claim_lunc_dividend")
@@ -194,13 +385,12 @@ impl<'a> FomoDFCState<'a> {
         info: MessageInfo,
         cycle: u64,
     ) -> Result<Response, ContractError> {
+        self.check_not_expired(deps.as_ref(), cycle)?;
         let user_cycle_ustc_dividend_withdrawed = self.user_cycle_ustc_dividend_withdrawed.may_load(deps.storage, (info.sender.clone(), U64Key::from(cycle)))?.unwrap_or(false);
         if user_cycle_ustc_dividend_withdrawed {
             return Err(ContractError::AlreadyWithdrawed {cycle});
         }
-        let cycle_avg_ustc_dividend = self.cycle_avg_ustc_dividend.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero());
-        let user_cycle_fires = self.user_cycle_fires.may_load(deps.storage, (info.sender.clone(), U64Key::from(cycle)))?.unwrap_or(0);
-        let user_left_lunc_dividend = cycle_avg_ustc_dividend * Uint128::from(user_cycle_fires);
+        let user_left_lunc_dividend = self.user_ustc_dividend(deps.storage, &info.sender, cycle)?;
 
         let mut messages: Vec<CosmosMsg> = vec![];
         if user_left_lunc_dividend > Uint128::zero() {
@@ -214,6 +404,8 @@ impl<'a> FomoDFCState<'a> {
         }
 
         self.user_cycle_ustc_dividend_withdrawed.save(deps.storage, (info.sender.clone(), U64Key::from(cycle)), &true)?;
+        self.record_dividends_claimed(deps.storage, &info.sender, Uint128::zero(), user_left_lunc_dividend)?;
+        self.record_cycle_claim(deps.storage, cycle, Uint128::zero(), user_left_lunc_dividend)?;
         let res = Response::new()
             .add_messages(messages)
             .add_attribute("action", "claim_lunc_dividend")
@@ -223,6 +415,531 @@ impl<'a> FomoDFCState<'a> {
         Ok(res)
     }    
     
+    pub fn claim_all_dividends(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        cycles: Option<Vec<u64>>,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let current_cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
+        let scan = cycles.is_none();
+        let cycles = match cycles {
+            Some(mut cycles) => {
+                cycles.sort_unstable();
+                cycles.dedup();
+                cycles.truncate(limit);
+                cycles
+            }
+            None => {
+                let cursor = self.user_claim_cursor.may_load(deps.storage, info.sender.clone())?;
+                let start = cursor.map(|cycle| Bound::exclusive(U64Key::from(cycle)));
+                self.user_cycle_fires
+                    .prefix(info.sender.clone())
+                    .range(deps.storage, start, None, Order::Ascending)
+                    .take(limit)
+                    .map(|item| item.map(|(key, _)| u64_from_key(&key)))
+                    .collect::<StdResult<Vec<u64>>>()?
+            }
+        };
+
+        let mut lunc_amount = Uint128::zero();
+        let mut ustc_amount = Uint128::zero();
+        let mut finished_cycle = None;
+        for cycle in cycles.iter() {
+            let key = (info.sender.clone(), U64Key::from(*cycle));
+            if !self.user_cycle_fires.has(deps.storage, key.clone()) {
+                continue;
+            }
+            // expired cycles fail when asked for, and are passed over when scanning
+            if self.is_expired(deps.storage, *cycle, current_cycle)? {
+                if !scan {
+                    self.check_not_expired(deps.as_ref(), *cycle)?;
+                }
+                finished_cycle = Some(*cycle);
+                continue;
+            }
+            let (user_left_lunc_dividend, mut user_left_ustc_dividend) = self.cycle_claimable(deps.storage, &info.sender, *cycle, current_cycle)?;
+            if user_left_lunc_dividend > Uint128::zero() {
+                lunc_amount += user_left_lunc_dividend;
+                self.user_cycle_dividend_withdrawed.update(deps.storage, key.clone(), |dividend| -> StdResult<_> {
+                    Ok(dividend.unwrap_or(Uint128::zero()) + user_left_lunc_dividend)
+                })?;
+            }
+            // the ustc dividend of a cycle is only known once the next cycle has opened
+            if *cycle < current_cycle {
+                ustc_amount += user_left_ustc_dividend;
+                self.user_cycle_ustc_dividend_withdrawed.save(deps.storage, key, &true)?;
+                finished_cycle = Some(*cycle);
+            } else {
+                user_left_ustc_dividend = Uint128::zero();
+            }
+            self.record_cycle_claim(deps.storage, *cycle, user_left_lunc_dividend, user_left_ustc_dividend)?;
+        }
+        if scan {
+            if let Some(finished_cycle) = finished_cycle {
+                self.user_claim_cursor.save(deps.storage, info.sender.clone(), &finished_cycle)?;
+            }
+        }
+        self.record_dividends_claimed(deps.storage, &info.sender, lunc_amount, ustc_amount)?;
+
+        let mut messages: Vec<CosmosMsg> = vec![];
+        if lunc_amount > Uint128::zero() {
+            messages.push(self.send_lunc(&deps.querier, info.sender.clone(), lunc_amount)?);
+        }
+        if ustc_amount > Uint128::zero() {
+            let ustc_dividend = Asset {
+                info: AssetInfo::NativeToken {
+                    denom: USTC_DENOM.to_string(),
+                },
+                amount: ustc_amount,
+            };
+            messages.push(ustc_dividend.into_msg(&deps.querier, info.sender.clone())?);
+        }
+
+        let res = Response::new()
+            .add_messages(messages)
+            .add_attribute("action", "claim_all_dividends")
+            .add_attribute("owner", info.sender)
+            .add_attribute("cycles", cycles.len().to_string())
+            .add_attribute("lunc_amount", lunc_amount.to_string())
+            .add_attribute("ustc_amount", ustc_amount.to_string());
+
+        Ok(res)
+    }
+
+    pub fn withdraw_referral_rewards(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let amount = self.pending_referral_rewards.may_load(deps.storage, info.sender.clone())?.unwrap_or(Uint128::zero());
+        if amount.is_zero() {
+            return Err(ContractError::NoRewards {});
+        }
+        self.pending_referral_rewards.remove(deps.storage, info.sender.clone());
+
+        let res = Response::new()
+            .add_message(self.send_lunc(&deps.querier, info.sender.clone(), amount)?)
+            .add_attribute("action", "withdraw_referral_rewards")
+            .add_attribute("owner", info.sender)
+            .add_attribute("amount", amount.to_string());
+
+        Ok(res)
+    }
+
+    // anyone can trigger it, the rewards always go to the dev address
+    pub fn withdraw_dev_rewards(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let amount = self.pending_dev_rewards.may_load(deps.storage)?.unwrap_or(Uint128::zero());
+        if amount.is_zero() {
+            return Err(ContractError::NoRewards {});
+        }
+        self.pending_dev_rewards.save(deps.storage, &Uint128::zero())?;
+
+        let dev_address = deps.api.addr_humanize(&config.dev_address)?;
+        let res = Response::new()
+            .add_message(self.send_lunc(&deps.querier, dev_address.clone(), amount)?)
+            .add_attribute("action", "withdraw_dev_rewards")
+            .add_attribute("sender", info.sender)
+            .add_attribute("dev_address", dev_address)
+            .add_attribute("amount", amount.to_string());
+
+        Ok(res)
+    }
+
+    pub fn transfer_ownership(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        owner: String,
+    ) -> Result<Response, ContractError> {
+        self.only_owner(deps.as_ref(), &info.sender)?;
+        let pending_owner = deps.api.addr_validate(owner.as_str())?;
+        self.pending_owner.save(deps.storage, &pending_owner)?;
+
+        let res = Response::new()
+            .add_attribute("action", "transfer_ownership")
+            .add_attribute("owner", info.sender)
+            .add_attribute("pending_owner", pending_owner);
+
+        Ok(res)
+    }
+
+    pub fn accept_ownership(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        match self.pending_owner.may_load(deps.storage)? {
+            Some(pending_owner) if pending_owner == info.sender => {}
+            _ => return Err(ContractError::NotPendingOwner { sender: info.sender.to_string() }),
+        }
+        self.owner.save(deps.storage, &info.sender)?;
+        self.pending_owner.remove(deps.storage);
+
+        let res = Response::new()
+            .add_attribute("action", "accept_ownership")
+            .add_attribute("owner", info.sender);
+
+        Ok(res)
+    }
+
+    // changes apply once the next round opens, or right away before the current round has a fire
+    pub fn update_config(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: UpdateConfigMsg,
+    ) -> Result<Response, ContractError> {
+        let active_config = CONFIG.load(deps.storage)?;
+        let sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+        if active_config.governance_address != Some(sender) {
+            self.only_owner(deps.as_ref(), &info.sender)?;
+        }
+
+        let mut config = PENDING_CONFIG.may_load(deps.storage)?.unwrap_or(active_config);
+        config.max_delay_time = msg.max_delay_time.unwrap_or(config.max_delay_time);
+        config.delay_time_per_burn = msg.delay_time_per_burn.unwrap_or(config.delay_time_per_burn);
+        config.dividend_percent = msg.dividend_percent.unwrap_or(config.dividend_percent);
+        config.burned_percent = msg.burned_percent.unwrap_or(config.burned_percent);
+        config.invite_percent = msg.invite_percent.unwrap_or(config.invite_percent);
+        config.dev_percent = msg.dev_percent.unwrap_or(config.dev_percent);
+        config.second_invite_percent = msg.second_invite_percent.unwrap_or(config.second_invite_percent);
+        config.unused_invite_destination = msg.unused_invite_destination.unwrap_or(config.unused_invite_destination);
+        config.keeper_tip_percent = msg.keeper_tip_percent.unwrap_or(config.keeper_tip_percent);
+        config.jackpot_carry_percent = msg.jackpot_carry_percent.unwrap_or(config.jackpot_carry_percent);
+        config.timer_policy = msg.timer_policy.unwrap_or(config.timer_policy);
+        if msg.clear_claim_window.unwrap_or(false) {
+            if msg.claim_window.is_some() {
+                return Err(ContractError::NotValidClaimWindow {});
+            }
+            config.claim_window = None;
+        }
+        if let Some(claim_window) = msg.claim_window {
+            config.claim_window = Some(claim_window);
+        }
+        config.prize_table = msg.prize_table.unwrap_or(config.prize_table);
+        if let Some(dev_address) = msg.dev_address {
+            config.dev_address = deps.api.addr_canonicalize(dev_address.as_str())?;
+        }
+        if let Some(dflunc_address) = msg.dflunc_address {
+            config.dflunc_address = deps.api.addr_canonicalize(dflunc_address.as_str())?;
+        }
+        if let Some(governance_address) = msg.governance_address {
+            config.governance_address = Some(deps.api.addr_canonicalize(governance_address.as_str())?);
+        }
+        self.validate_prize_table(&config.prize_table)?;
+        self.validate_keeper_tip(config.keeper_tip_percent)?;
+        self.validate_jackpot_carry(config.jackpot_carry_percent)?;
+        self.validate_timer_policy(&config.timer_policy)?;
+        self.validate_claim_window(config.claim_window)?;
+        self.validate_split(&config)?;
+
+        let current_cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
+        let total_fires = self.cycle_total_fires.may_load(deps.storage, U64Key::from(current_cycle))?.unwrap_or(0);
+        let effective_cycle = if total_fires == 0 {
+            CONFIG.save(deps.storage, &config)?;
+            PENDING_CONFIG.remove(deps.storage);
+            current_cycle
+        } else {
+            PENDING_CONFIG.save(deps.storage, &config)?;
+            current_cycle + 1
+        };
+
+        let res = Response::new()
+            .add_attribute("action", "update_config")
+            .add_attribute("sender", info.sender)
+            .add_attribute("effective_cycle", effective_cycle.to_string());
+
+        Ok(res)
+    }
+
+    pub fn set_team(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        team_id: u64,
+        team: Team,
+    ) -> Result<Response, ContractError> {
+        self.only_owner(deps.as_ref(), &info.sender)?;
+        self.validate_team(&team)?;
+        self.teams.save(deps.storage, U64Key::from(team_id), &team)?;
+
+        let res = Response::new()
+            .add_attribute("action", "set_team")
+            .add_attribute("team", team_id.to_string())
+            .add_attribute("name", team.name);
+
+        Ok(res)
+    }
+
+    pub fn remove_team(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        team_id: u64,
+    ) -> Result<Response, ContractError> {
+        self.only_owner(deps.as_ref(), &info.sender)?;
+        if !self.teams.has(deps.storage, U64Key::from(team_id)) {
+            return Err(ContractError::TeamNotFound { team_id });
+        }
+        self.teams.remove(deps.storage, U64Key::from(team_id));
+
+        let res = Response::new()
+            .add_attribute("action", "remove_team")
+            .add_attribute("team", team_id.to_string());
+
+        Ok(res)
+    }
+
+    // the unclaimed lunc joins the lunc pot of the current round and the unclaimed ustc its ustc pot
+    pub fn sweep_expired(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        cycle: u64,
+    ) -> Result<Response, ContractError> {
+        let current_cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
+        let claims_tracked_from = self.claims_tracked_from.may_load(deps.storage)?.unwrap_or(0);
+        if !self.is_expired(deps.storage, cycle, current_cycle)? || cycle < claims_tracked_from {
+            return Err(ContractError::NotSweepable { cycle });
+        }
+
+        let total_fires = self.cycle_total_fires.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(0);
+        let lunc_dividend = self.cycle_total_dividend.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero());
+        let avg_ustc_dividend = self.cycle_avg_ustc_dividend.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero());
+        let mut ustc_dividend = avg_ustc_dividend * Uint128::from(total_fires);
+        if let Some(team_id) = self.cycle_last_team.may_load(deps.storage, U64Key::from(cycle))? {
+            let avg_ustc_team_dividend = self.cycle_avg_ustc_team_dividend.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero());
+            let team_fires = self.cycle_team_fires.may_load(deps.storage, (U64Key::from(cycle), U64Key::from(team_id)))?.unwrap_or(0);
+            ustc_dividend += avg_ustc_team_dividend * Uint128::from(team_fires);
+        }
+
+        let lunc_claimed = self.cycle_lunc_dividend_claimed.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero());
+        let ustc_claimed = self.cycle_ustc_dividend_claimed.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero());
+        let lunc_amount = lunc_dividend.saturating_sub(lunc_claimed);
+        let ustc_amount = ustc_dividend.saturating_sub(ustc_claimed);
+        if lunc_amount.is_zero() && ustc_amount.is_zero() {
+            return Err(ContractError::NoRewards {});
+        }
+        // counted as claimed so the cycle can't be swept twice
+        self.record_cycle_claim(deps.storage, cycle, lunc_amount, ustc_amount)?;
+
+        if !lunc_amount.is_zero() {
+            self.cycle_lunc_pot.update(deps.storage, U64Key::from(current_cycle), |pot| -> StdResult<_> {
+                Ok(pot.unwrap_or(Uint128::zero()) + lunc_amount)
+            })?;
+        }
+        if !ustc_amount.is_zero() {
+            let ustc_jackpot_carry = self.ustc_jackpot_carry.may_load(deps.storage)?.unwrap_or(Uint128::zero());
+            self.ustc_jackpot_carry.save(deps.storage, &(ustc_jackpot_carry + ustc_amount))?;
+        }
+
+        let res = Response::new()
+            .add_attribute("action", "sweep_expired")
+            .add_attribute("sender", info.sender)
+            .add_attribute("cycle", cycle.to_string())
+            .add_attribute("lunc_amount", lunc_amount.to_string())
+            .add_attribute("ustc_amount", ustc_amount.to_string());
+
+        Ok(res)
+    }
+
+    fn is_expired(&self, storage: &dyn Storage, cycle: u64, current_cycle: u64) -> StdResult<bool> {
+        match self.cycle_claim_window.may_load(storage, U64Key::from(cycle))? {
+            Some(claim_window) => Ok(cycle.saturating_add(claim_window) < current_cycle),
+            None => Ok(false),
+        }
+    }
+
+    fn check_not_expired(&self, deps: Deps, cycle: u64) -> Result<(), ContractError> {
+        let current_cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
+        if self.is_expired(deps.storage, cycle, current_cycle)? {
+            let claim_window = self.cycle_claim_window.load(deps.storage, U64Key::from(cycle))?;
+            return Err(ContractError::DividendExpired { cycle, claim_window });
+        }
+        Ok(())
+    }
+
+    fn record_cycle_claim(&self, storage: &mut dyn Storage, cycle: u64, lunc_amount: Uint128, ustc_amount: Uint128) -> StdResult<()> {
+        if !lunc_amount.is_zero() {
+            self.cycle_lunc_dividend_claimed.update(storage, U64Key::from(cycle), |claimed| -> StdResult<_> {
+                Ok(claimed.unwrap_or(Uint128::zero()) + lunc_amount)
+            })?;
+        }
+        if !ustc_amount.is_zero() {
+            self.cycle_ustc_dividend_claimed.update(storage, U64Key::from(cycle), |claimed| -> StdResult<_> {
+                Ok(claimed.unwrap_or(Uint128::zero()) + ustc_amount)
+            })?;
+        }
+        Ok(())
+    }
+
+    fn only_owner(&self, deps: Deps, sender: &Addr) -> Result<(), ContractError> {
+        let owner = self.owner.may_load(deps.storage)?;
+        if owner.as_ref() != Some(sender) {
+            return Err(ContractError::NotOwner {
+                sender: sender.to_string(),
+                owner: owner.map(|owner| owner.to_string()).unwrap_or_default(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn settle_round(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        let current_cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
+        let end_time = self.end_time.may_load(deps.storage)?.unwrap_or(0);
+        let total_fires = self.cycle_total_fires.may_load(deps.storage, U64Key::from(current_cycle))?.unwrap_or(0);
+        if env.block.time.seconds() <= end_time {
+            return Err(ContractError::RoundNotEnded { end_time });
+        }
+        if total_fires == 0 {
+            return Err(ContractError::NoFireInCycle { cycle: current_cycle });
+        }
+
+        let messages = self.settle_cycle(
+            deps.api,
+            &deps.querier,
+            deps.storage,
+            env,
+            &config,
+            current_cycle,
+            total_fires,
+            Some(info.sender.clone()),
+        )?;
+
+        let res = Response::new()
+            .add_messages(messages)
+            .add_attribute("action", "settle_round")
+            .add_attribute("keeper", info.sender)
+            .add_attribute("cycle", current_cycle.to_string());
+
+        Ok(res)
+    }
+
+    // ustc reserved for the round by dflunc and not claimed yet
+    fn reserved_ustc(&self, querier: &QuerierWrapper, api: &dyn Api, config: &Config) -> StdResult<Uint128> {
+        let dfc_base_state: GetBaseStateResponse =
+            querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: api.addr_humanize(&config.dflunc_address)?.to_string(),
+                msg: to_binary(&QueryMsg::GetBaseState {})?,
+            }))?;
+        Ok(dfc_base_state.total_protocol_fees_reserved - dfc_base_state.withdrawed_protocol_fees_reserved)
+    }
+
+    // pays out the ustc of an ended cycle and opens the next one, the keeper gets a tip of the ustc
+    #[allow(clippy::too_many_arguments)]
+    fn settle_cycle(
+        &self,
+        api: &dyn Api,
+        querier: &QuerierWrapper,
+        storage: &mut dyn Storage,
+        env: Env,
+        config: &Config,
+        cycle: u64,
+        total_fires: u64,
+        keeper: Option<Addr>,
+    ) -> Result<Vec<CosmosMsg>, ContractError> {
+        let mut messages: Vec<CosmosMsg> = vec![];
+        let ustc_claimed_amount = self.reserved_ustc(querier, api, config)?;
+        if ustc_claimed_amount > Uint128::zero() {
+            let claim_ustc_from_dflunc_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: api.addr_humanize(&config.dflunc_address)?.to_string(),
+                msg: to_binary(&DfluncExecuteMsg::ClaimUstcReservedFees {
+                })?,
+                funds: vec![],
+            });
+            messages.push(claim_ustc_from_dflunc_msg);
+        }
+
+        // the pot is the claimed ustc with what the last round carried over
+        let ustc_jackpot_carry = self.ustc_jackpot_carry.may_load(storage)?.unwrap_or(Uint128::zero());
+        let ustc_pot = ustc_claimed_amount + ustc_jackpot_carry;
+        if ustc_pot > Uint128::zero() {
+            let mut ustc_to_distribute = ustc_pot;
+            if let Some(keeper) = keeper {
+                let keeper_tip = ustc_pot.multiply_ratio(config.keeper_tip_percent, MAX_BPS);
+                if keeper_tip > Uint128::zero() {
+                    let keeper_tip_asset = Asset {
+                        info: AssetInfo::NativeToken {
+                            denom: USTC_DENOM.to_string(),
+                        },
+                        amount: keeper_tip,
+                    };
+                    messages.push(keeper_tip_asset.into_msg(querier, keeper)?);
+                    ustc_to_distribute -= keeper_tip;
+                }
+            }
+            let new_ustc_jackpot_carry = ustc_to_distribute.multiply_ratio(config.jackpot_carry_percent, MAX_BPS);
+            self.ustc_jackpot_carry.save(storage, &new_ustc_jackpot_carry)?;
+            ustc_to_distribute -= new_ustc_jackpot_carry;
+
+            let messages_ustc_rewards = self.distribute_ustc(
+                querier,
+                storage,
+                env,
+                ustc_to_distribute,
+                cycle,
+                total_fires,
+                &config.prize_table,
+            )?;
+            messages.extend(messages_ustc_rewards);
+        }
+
+        if let Some(last_burner) = self.cycle_last_burner.may_load(storage, U64Key::from(cycle))? {
+            let lunc_pot = self.cycle_lunc_pot.may_load(storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero());
+            // credited rather than sent, so the burn that settles the round still sends lunc at most once
+            if !lunc_pot.is_zero() {
+                self.user_lunc_deposit.update(storage, last_burner.clone(), |deposit| -> StdResult<_> {
+                    Ok(deposit.unwrap_or(Uint128::zero()) + lunc_pot)
+                })?;
+            }
+            let mut stats = self.player_stats.may_load(storage, last_burner.clone())?.unwrap_or_default();
+            stats.rounds_won += 1;
+            self.player_stats.save(storage, last_burner, &stats)?;
+        }
+
+        // later changes of the claim window leave the rounds settled before alone
+        if let Some(claim_window) = config.claim_window {
+            self.cycle_claim_window.save(storage, U64Key::from(cycle), &claim_window)?;
+        }
+
+        // config updates made during the round apply from the next one
+        let mut config = config.clone();
+        if let Some(pending_config) = PENDING_CONFIG.may_load(storage)? {
+            CONFIG.save(storage, &pending_config)?;
+            PENDING_CONFIG.remove(storage);
+            config = pending_config;
+        }
+
+        let next_cycle = cycle + 1;
+        self.current_cycle.save(storage, &next_cycle)?;
+        self.lunc_amount_in_required.save(storage, U64Key::from(next_cycle), &config.initial_lunc_amount_in)?;
+        self.cycle_total_fires.save(storage, U64Key::from(next_cycle), &0)?;
+        Ok(messages)
+    }
+
     fn distribute_ustc(
         &self,
         querier: &QuerierWrapper,
@@ -231,83 +948,155 @@ impl<'a> FomoDFCState<'a> {
         ustc_claimed_amount: Uint128,
         cycle: u64,
         total_fires: u64,
-        ustc_last_fire_numerator: u64,
-        ustc_last_fire_denominator: u64,
+        prize_table: &PrizeTable,
     ) -> Result<Vec<CosmosMsg>, ContractError> {
-        let mut messages: Vec<CosmosMsg> = vec![];             
+        let mut messages: Vec<CosmosMsg> = vec![];
 
-        // 1: 2/3 ustc to last burner
-        let cycle_last_burner = self.cycle_last_burner.may_load(storage, U64Key::from(cycle))?.unwrap();
-        let ustc_amount_to_last_burner = ustc_claimed_amount * Uint128::from(ustc_last_fire_numerator) / Uint128::from(ustc_last_fire_denominator);
-        let ustc_rewards_to_last_burner = Asset {
-            info: AssetInfo::NativeToken {
-                denom: USTC_DENOM.to_string(),
-            },
-            amount: ustc_amount_to_last_burner,
-        };
-        let ustc_rewards_message = ustc_rewards_to_last_burner.into_msg(&querier, cycle_last_burner)?;
-        messages.push(ustc_rewards_message);
+        // 1: prizes by the prize table, shares without a winner stay with the fires
+        let winners = self.cycle_winners(storage, cycle, prize_table)?;
 
-        self.cycle_last_burner_rewards.save(storage, U64Key::from(cycle), &ustc_amount_to_last_burner)?;
+        let mut prizes: Vec<Prize> = vec![];
+        let mut payouts: Vec<(Addr, Uint128)> = vec![];
+        let mut ustc_amount_to_winners = Uint128::zero();
+        for (winner, tier, percent) in winners {
+            let amount = ustc_claimed_amount.multiply_ratio(percent, MAX_BPS);
+            if amount.is_zero() {
+                continue;
+            }
+            ustc_amount_to_winners += amount;
+            match payouts.iter_mut().find(|(addr, _)| *addr == winner) {
+                Some((_, total)) => *total += amount,
+                None => payouts.push((winner.clone(), amount)),
+            }
+            if tier == PrizeTier::LastBurner {
+                self.cycle_last_burner_rewards.save(storage, U64Key::from(cycle), &amount)?;
+            }
+            let mut stats = self.player_stats.may_load(storage, winner.clone())?.unwrap_or_default();
+            stats.ustc_prizes_won += amount;
+            self.player_stats.save(storage, winner.clone(), &stats)?;
+            prizes.push(Prize { winner, tier, amount });
+        }
+        // one send per winner, even when it won several tiers
+        for (winner, amount) in payouts {
+            let ustc_prize = Asset {
+                info: AssetInfo::NativeToken {
+                    denom: USTC_DENOM.to_string(),
+                },
+                amount,
+            };
+            messages.push(ustc_prize.into_msg(querier, winner)?);
+        }
+        self.cycle_prizes.save(storage, U64Key::from(cycle), &prizes)?;
 
-        // 2: 1/3 reward was distributed equally among existing fires.
-        let left_ustc_rewards = ustc_claimed_amount - ustc_amount_to_last_burner;
-        let avg_ustc_rewards = left_ustc_rewards / Uint128::from(total_fires);
+        // 2: the rest of the reward was distributed among existing fires, the winning team's fires first,
+        // with the remainder of the previous round, and the new remainder is carried to the next round.
+        let ustc_dividend_carry = self.ustc_dividend_carry.may_load(storage)?.unwrap_or(Uint128::zero());
+        let left_ustc_rewards = ustc_claimed_amount - ustc_amount_to_winners + ustc_dividend_carry;
+        let (avg_ustc_rewards, avg_ustc_team_rewards, new_ustc_dividend_carry) = self.split_ustc_dividend(storage, cycle, total_fires, left_ustc_rewards)?;
+        self.ustc_dividend_carry.save(storage, &new_ustc_dividend_carry)?;
         self.cycle_avg_ustc_dividend.save(storage, U64Key::from(cycle), &avg_ustc_rewards)?;
+        self.cycle_avg_ustc_team_dividend.save(storage, U64Key::from(cycle), &avg_ustc_team_rewards)?;
 
         Ok(messages)
     }
 
-    fn distribute_rewards_to_inviter(
+    // the inviter is bound on the first burn and can't change afterwards,
+    // and only counts if it has burned at least once itself
+    fn resolve_inviter(
         &self,
         api: &dyn Api,
-        querier: &QuerierWrapper,
         storage: &mut dyn Storage,
-        messages: &mut Vec<CosmosMsg>,
-        lunc_amount_in: Uint128,
+        sender: &Addr,
         invite_address: Option<String>,
-        invite_percent: u64,
-    ) -> Result<Uint128, ContractError> {
-        if invite_address.is_some() {
-            let invited_addr= api.addr_validate(&invite_address.unwrap())?;
+    ) -> Result<Option<Addr>, ContractError> {
+        let invited_addr = match invite_address {
+            Some(invite_address) => Some(api.addr_validate(&invite_address)?),
+            None => None,
+        };
+        if invited_addr.as_ref() == Some(sender) {
+            return Err(ContractError::SelfInvite {});
+        }
 
-            let user_burned_at_least_once = self.user_burned_at_least_once.may_load(storage, invited_addr.clone())?.unwrap_or(false);
-            if !user_burned_at_least_once {
-                return Ok(Uint128::zero());
+        if let Some(inviter) = self.user_invited_address.may_load(storage, sender.clone())? {
+            return Ok(Some(inviter));
+        }
+        if self.user_burned_at_least_once.may_load(storage, sender.clone())?.unwrap_or(false) {
+            return Ok(None);
+        }
+        if let Some(invited_addr) = invited_addr {
+            if self.user_burned_at_least_once.may_load(storage, invited_addr.clone())?.unwrap_or(false) {
+                self.user_invited_address.save(storage, sender.clone(), &invited_addr)?;
+                self.user_invitees.save(storage, (invited_addr.clone(), sender.clone()), &true)?;
+                self.user_invitee_count.update(storage, invited_addr.clone(), |count| -> StdResult<_> {
+                    Ok(count.unwrap_or(0) + 1)
+                })?;
+                return Ok(Some(invited_addr));
             }
-            
-            let lunc_amount = lunc_amount_in * Uint128::from(invite_percent) / Uint128::from(MAX_BPS);
-            messages.push(self.send_lunc(&querier, invited_addr, lunc_amount)?);
-            return Ok(lunc_amount);
         }
-        
-        Ok(Uint128::zero())
+        Ok(None)
     }
 
-    fn burn_lunc(
+    fn split_fire(
         &self,
-        deps: Deps,
-        messages: &mut Vec<CosmosMsg>,
         lunc_amount_in: Uint128,
-        burned_address: Addr,
-        burned_percent: u64,
-    ) -> Result<Uint128, ContractError> {
-        let lunc_amount = lunc_amount_in * Uint128::from(burned_percent) / Uint128::from(MAX_BPS);
-        messages.push(self.send_lunc(&deps.querier, burned_address, lunc_amount)?);
-        return Ok(lunc_amount);
+        config: &Config,
+        team: Option<&Team>,
+        has_inviter: bool,
+        has_second_inviter: bool,
+    ) -> LuncSplit {
+        let mut dividend = lunc_amount_in.multiply_ratio(config.dividend_percent, MAX_BPS);
+        let invite = lunc_amount_in.multiply_ratio(config.invite_percent, MAX_BPS);
+        let second_invite = lunc_amount_in.multiply_ratio(config.second_invite_percent, MAX_BPS);
+        let dev = lunc_amount_in.multiply_ratio(config.dev_percent, MAX_BPS);
+        let burned = lunc_amount_in.multiply_ratio(config.burned_percent, MAX_BPS);
+        // the percents sum to MAX_BPS, so only rounding dust is left and it is burned
+        let dust = lunc_amount_in - dividend - invite - second_invite - dev - burned;
+        let mut burned = burned + dust;
+        let mut pot = Uint128::zero();
+        if let Some(team) = team {
+            // the team splits the dividend and burn share between dividend, burn and the pot
+            let team_share = dividend + burned;
+            dividend = team_share.multiply_ratio(team.dividend_percent, MAX_BPS);
+            pot = team_share.multiply_ratio(team.pot_percent, MAX_BPS);
+            burned = team_share - dividend - pot;
+        }
+        let mut split = LuncSplit {
+            lunc_amount_in,
+            dividend,
+            burned,
+            invite,
+            second_invite,
+            dev,
+            pot,
+        };
+
+        let mut unused_invite = Uint128::zero();
+        if !has_inviter {
+            unused_invite += split.invite;
+            split.invite = Uint128::zero();
+        }
+        if !has_second_inviter {
+            unused_invite += split.second_invite;
+            split.second_invite = Uint128::zero();
+        }
+        match config.unused_invite_destination {
+            UnusedInviteDestination::Dividend => split.dividend += unused_invite,
+            UnusedInviteDestination::Burn => split.burned += unused_invite,
+            UnusedInviteDestination::Dev => split.dev += unused_invite,
+        }
+        split
     }
 
-    fn distribute_dev_rewards(
-        &self,
-        deps: Deps,
-        messages: &mut Vec<CosmosMsg>,
-        lunc_amount_in: Uint128,
-        dev_address: Addr,
-        dev_percent: u64,
-    ) -> Result<Uint128, ContractError> {
-        let lunc_amount = lunc_amount_in * Uint128::from(dev_percent) / Uint128::from(MAX_BPS);
-        messages.push(self.send_lunc(&deps.querier, dev_address, lunc_amount)?);
-        return Ok(lunc_amount);
+    pub fn validate_split(&self, config: &Config) -> Result<(), ContractError> {
+        let total = Uint128::from(config.dividend_percent)
+            + Uint128::from(config.burned_percent)
+            + Uint128::from(config.invite_percent)
+            + Uint128::from(config.second_invite_percent)
+            + Uint128::from(config.dev_percent);
+        if total != Uint128::from(MAX_BPS) {
+            return Err(ContractError::NotValidSplit { total });
+        }
+        Ok(())
     }
 
     fn send_lunc(
@@ -326,6 +1115,92 @@ impl<'a> FomoDFCState<'a> {
         Ok(message)
     }
 
+    // keeps the dividend rankings in step with the claimed dividends
+    fn record_dividends_claimed(
+        &self,
+        storage: &mut dyn Storage,
+        user: &Addr,
+        lunc_amount: Uint128,
+        ustc_amount: Uint128,
+    ) -> StdResult<()> {
+        if lunc_amount.is_zero() && ustc_amount.is_zero() {
+            return Ok(());
+        }
+        let mut stats = self.player_stats.may_load(storage, user.clone())?.unwrap_or_default();
+        if !lunc_amount.is_zero() {
+            self.lunc_dividend_ranking.remove(storage, (U128Key::from(stats.lunc_dividends_claimed.u128()), user.clone()));
+            stats.lunc_dividends_claimed += lunc_amount;
+            self.lunc_dividend_ranking.save(storage, (U128Key::from(stats.lunc_dividends_claimed.u128()), user.clone()), user)?;
+        }
+        if !ustc_amount.is_zero() {
+            self.ustc_dividend_ranking.remove(storage, (U128Key::from(stats.ustc_dividends_claimed.u128()), user.clone()));
+            stats.ustc_dividends_claimed += ustc_amount;
+            self.ustc_dividend_ranking.save(storage, (U128Key::from(stats.ustc_dividends_claimed.u128()), user.clone()), user)?;
+        }
+        self.player_stats.save(storage, user.clone(), &stats)
+    }
+
+    // winners of the cycle with their tier and share
+    fn cycle_winners(
+        &self,
+        storage: &dyn Storage,
+        cycle: u64,
+        prize_table: &PrizeTable,
+    ) -> StdResult<Vec<(Addr, PrizeTier, u64)>> {
+        let mut recent_burners = self.cycle_recent_burners.may_load(storage, U64Key::from(cycle))?.unwrap_or_default();
+        // rounds running across the upgrade only know their last burner
+        if recent_burners.is_empty() {
+            if let Some(last_burner) = self.cycle_last_burner.may_load(storage, U64Key::from(cycle))? {
+                recent_burners.push(last_burner);
+            }
+        }
+        let mut winners: Vec<(Addr, PrizeTier, u64)> = vec![];
+        if let Some(last_burner) = recent_burners.first() {
+            winners.push((last_burner.clone(), PrizeTier::LastBurner, prize_table.last_burner_percent));
+        }
+        for (rank, (burner, percent)) in recent_burners.iter().skip(1).zip(prize_table.previous_burner_percents.iter()).enumerate() {
+            winners.push((burner.clone(), PrizeTier::PreviousBurner { rank: rank as u64 + 1 }, *percent));
+        }
+        if let Some((top_burner, _)) = self.cycle_top_burner.may_load(storage, U64Key::from(cycle))? {
+            winners.push((top_burner, PrizeTier::TopBurner, prize_table.top_burner_percent));
+        }
+        Ok(winners)
+    }
+
+    // keeps the latest distinct burners, most recent first, and the burner with the most fires
+    fn update_cycle_winners(
+        &self,
+        storage: &mut dyn Storage,
+        burner: &Addr,
+        cycle: u64,
+    ) -> StdResult<()> {
+        let mut recent_burners = self.cycle_recent_burners.may_load(storage, U64Key::from(cycle))?.unwrap_or_default();
+        recent_burners.retain(|recent_burner| recent_burner != burner);
+        recent_burners.insert(0, burner.clone());
+        recent_burners.truncate(MAX_PREVIOUS_BURNERS + 1);
+        self.cycle_recent_burners.save(storage, U64Key::from(cycle), &recent_burners)?;
+
+        let fires = self.user_cycle_fires.may_load(storage, (burner.clone(), U64Key::from(cycle)))?.unwrap_or(0);
+        let top_fires = self.cycle_top_burner.may_load(storage, U64Key::from(cycle))?.map(|(_, top_fires)| top_fires);
+        if top_fires.unwrap_or(0) < fires {
+            self.cycle_top_burner.save(storage, U64Key::from(cycle), &(burner.clone(), fires))?;
+        }
+        Ok(())
+    }
+
+    pub fn validate_prize_table(&self, prize_table: &PrizeTable) -> Result<(), ContractError> {
+        let previous = &prize_table.previous_burner_percents;
+        let total = Uint128::from(prize_table.last_burner_percent)
+            + Uint128::from(prize_table.top_burner_percent)
+            + previous.iter().map(|percent| Uint128::from(*percent)).sum::<Uint128>();
+        if previous.len() > MAX_PREVIOUS_BURNERS
+            || previous.windows(2).any(|pair| pair[0] < pair[1])
+            || total > Uint128::from(MAX_BPS) {
+            return Err(ContractError::NotValidPrizeTable {});
+        }
+        Ok(())
+    }
+
     fn calculate_dividend(
         &self,
         storage: &mut dyn Storage,
@@ -334,7 +1209,12 @@ impl<'a> FomoDFCState<'a> {
         total_fires: u64,
         current_cycle: u64,
     ) -> Result<(), ContractError> {        
-        let new_avg_lunc_per_fire = left_lunc_to_dividend / Uint128::from(total_fires);
+        // the remainder that can't be split evenly is carried into the next dividend
+        let lunc_dividend_carry = self.lunc_dividend_carry.may_load(storage)?.unwrap_or(Uint128::zero());
+        let lunc_to_dividend = left_lunc_to_dividend + lunc_dividend_carry;
+        let new_avg_lunc_per_fire = lunc_to_dividend / Uint128::from(total_fires);
+        let lunc_dividend = new_avg_lunc_per_fire * Uint128::from(total_fires);
+        self.lunc_dividend_carry.save(storage, &(lunc_to_dividend - lunc_dividend))?;
         let mut avg_lunc_dividend_per_fire = self.cycle_avg_lunc_dividend.may_load(storage, U64Key::from(current_cycle))?.unwrap_or(Uint128::zero());
         avg_lunc_dividend_per_fire += new_avg_lunc_per_fire;
         self.cycle_avg_lunc_dividend.save(storage, U64Key::from(current_cycle), &avg_lunc_dividend_per_fire)?;
@@ -346,11 +1226,165 @@ impl<'a> FomoDFCState<'a> {
             Ok(dividend.unwrap_or(Uint128::zero()) + avg_lunc_dividend_per_fire)
         })?;
         self.cycle_total_dividend.update(storage, U64Key::from(current_cycle), |dividend| -> StdResult<_> {
-            Ok(dividend.unwrap_or(Uint128::zero()) + left_lunc_to_dividend)
+            Ok(dividend.unwrap_or(Uint128::zero()) + lunc_dividend)
         })?;
         Ok(())
     }
 
+    pub fn validate_team(&self, team: &Team) -> Result<(), ContractError> {
+        let total = Uint128::from(team.dividend_percent)
+            + Uint128::from(team.burned_percent)
+            + Uint128::from(team.pot_percent);
+        if total != Uint128::from(MAX_BPS) || team.ustc_share_percent > MAX_BPS {
+            return Err(ContractError::NotValidTeam {});
+        }
+        Ok(())
+    }
+
+    pub fn validate_price_step(&self, price_step: &PriceStep) -> Result<(), ContractError> {
+        let valid = match price_step {
+            PriceStep::Linear { increment } => !increment.is_zero(),
+            PriceStep::Exponential { rate } => *rate > 0 && *rate <= MAX_BPS,
+        };
+        if !valid {
+            return Err(ContractError::NotValidPriceStep {});
+        }
+        Ok(())
+    }
+
+    pub fn validate_jackpot_carry(&self, jackpot_carry_percent: u64) -> Result<(), ContractError> {
+        if jackpot_carry_percent > MAX_BPS {
+            return Err(ContractError::NotValidJackpotCarry {});
+        }
+        Ok(())
+    }
+
+    pub fn validate_keeper_tip(&self, keeper_tip_percent: u64) -> Result<(), ContractError> {
+        if keeper_tip_percent > MAX_BPS {
+            return Err(ContractError::NotValidKeeperTip {});
+        }
+        Ok(())
+    }
+
+    pub fn validate_claim_window(&self, claim_window: Option<u64>) -> Result<(), ContractError> {
+        if claim_window == Some(0) {
+            return Err(ContractError::NotValidClaimWindow {});
+        }
+        Ok(())
+    }
+
+    pub fn validate_timer_policy(&self, timer_policy: &TimerPolicy) -> Result<(), ContractError> {
+        if timer_policy.pot_step == Some(Uint128::zero())
+            || timer_policy.age_step == Some(0)
+            || timer_policy.max_round_length == Some(0)
+            || timer_policy.pot_decay_percent > MAX_BPS
+            || timer_policy.age_decay_percent > MAX_BPS
+            || timer_policy.final_min_price_increase_percent > MAX_BPS {
+            return Err(ContractError::NotValidTimerPolicy {});
+        }
+        Ok(())
+    }
+
+    // seconds a fire adds to the timer, shrinking as the ustc pot grows and the round ages
+    fn delay_time_per_fire(&self, deps: Deps, config: &Config, cycle: u64, current_time: u64) -> StdResult<u64> {
+        let timer_policy = &config.timer_policy;
+        let mut decay_percent: u64 = 0;
+        if let Some(pot_step) = timer_policy.pot_step {
+            let ustc_jackpot_carry = self.ustc_jackpot_carry.may_load(deps.storage)?.unwrap_or(Uint128::zero());
+            let ustc_pot = self.reserved_ustc(&deps.querier, deps.api, config)? + ustc_jackpot_carry;
+            let pot_steps = (ustc_pot.u128() / pot_step.u128()).min(MAX_BPS as u128) as u64;
+            decay_percent += timer_policy.pot_decay_percent * pot_steps;
+        }
+        if let Some(age_step) = timer_policy.age_step {
+            let start_time = self.cycle_start_time.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(current_time);
+            let age_steps = (current_time.saturating_sub(start_time) / age_step).min(MAX_BPS);
+            decay_percent += timer_policy.age_decay_percent * age_steps;
+        }
+
+        let delay_time_per_burn = Uint128::from(config.delay_time_per_burn)
+            .multiply_ratio(MAX_BPS - decay_percent.min(MAX_BPS), MAX_BPS)
+            .u128() as u64;
+        Ok(delay_time_per_burn.max(timer_policy.min_delay_time_per_burn.min(config.delay_time_per_burn)))
+    }
+
+    // in the final seconds of the timer the price rises by at least the minimum of the timer policy
+    fn next_fire_price(&self, lunc_amount_in: Uint128, config: &Config, in_final_window: bool) -> StdResult<Uint128> {
+        let next_lunc_amount_in = self.next_lunc_amount_in(lunc_amount_in, &config.price_step)?;
+        if !in_final_window {
+            return Ok(next_lunc_amount_in);
+        }
+        let min_increase = lunc_amount_in.multiply_ratio(config.timer_policy.final_min_price_increase_percent, MAX_BPS);
+        Ok(next_lunc_amount_in.max(lunc_amount_in.checked_add(min_increase)?))
+    }
+
+    fn next_lunc_amount_in(&self, lunc_amount_in: Uint128, price_step: &PriceStep) -> StdResult<Uint128> {
+        let increment = match price_step {
+            PriceStep::Linear { increment } => *increment,
+            PriceStep::Exponential { rate } => lunc_amount_in.multiply_ratio(*rate, MAX_BPS),
+        };
+        Ok(lunc_amount_in.checked_add(increment)?)
+    }
+
+    // unclaimed lunc and ustc dividends of the user in the cycle
+    fn cycle_claimable(
+        &self,
+        storage: &dyn Storage,
+        user: &Addr,
+        cycle: u64,
+        current_cycle: u64,
+    ) -> StdResult<(Uint128, Uint128)> {
+        let key = (user.clone(), U64Key::from(cycle));
+        let user_cycle_fires = Uint128::from(self.user_cycle_fires.may_load(storage, key.clone())?.unwrap_or(0));
+
+        let cycle_avg_lunc_dividend = self.cycle_avg_lunc_dividend.may_load(storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero());
+        let user_cycle_dividend_withdrawed = self.user_cycle_dividend_withdrawed.may_load(storage, key.clone())?.unwrap_or(Uint128::zero());
+        let lunc_amount = (cycle_avg_lunc_dividend * user_cycle_fires).saturating_sub(user_cycle_dividend_withdrawed);
+
+        let mut ustc_amount = Uint128::zero();
+        if cycle < current_cycle && !self.user_cycle_ustc_dividend_withdrawed.may_load(storage, key)?.unwrap_or(false) {
+            ustc_amount = self.user_ustc_dividend(storage, user, cycle)?;
+        }
+        Ok((lunc_amount, ustc_amount))
+    }
+
+    // the ustc dividend of the user's fires, with the winning team's share for the fires in that team
+    fn user_ustc_dividend(&self, storage: &dyn Storage, user: &Addr, cycle: u64) -> StdResult<Uint128> {
+        let user_cycle_fires = self.user_cycle_fires.may_load(storage, (user.clone(), U64Key::from(cycle)))?.unwrap_or(0);
+        let cycle_avg_ustc_dividend = self.cycle_avg_ustc_dividend.may_load(storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero());
+        let mut ustc_amount = cycle_avg_ustc_dividend * Uint128::from(user_cycle_fires);
+        if let Some(team_id) = self.cycle_last_team.may_load(storage, U64Key::from(cycle))? {
+            let avg_ustc_team_dividend = self.cycle_avg_ustc_team_dividend.may_load(storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero());
+            let user_team_fires = self.user_cycle_team_fires.may_load(storage, (user.clone(), U64Key::from(cycle), U64Key::from(team_id)))?.unwrap_or(0);
+            ustc_amount += avg_ustc_team_dividend * Uint128::from(user_team_fires);
+        }
+        Ok(ustc_amount)
+    }
+
+    // the winning team's share goes to its own fires, the rest to every fire of the cycle;
+    // returns the dividend per fire, the extra per team fire and the remainder to carry
+    fn split_ustc_dividend(
+        &self,
+        storage: &dyn Storage,
+        cycle: u64,
+        total_fires: u64,
+        ustc_dividend: Uint128,
+    ) -> StdResult<(Uint128, Uint128, Uint128)> {
+        let mut avg_ustc_team_dividend = Uint128::zero();
+        let mut ustc_team_dividend = Uint128::zero();
+        if let Some(team_id) = self.cycle_last_team.may_load(storage, U64Key::from(cycle))? {
+            let team_fires = self.cycle_team_fires.may_load(storage, (U64Key::from(cycle), U64Key::from(team_id)))?.unwrap_or(0);
+            if let Some(team) = self.teams.may_load(storage, U64Key::from(team_id))? {
+                if team_fires > 0 {
+                    avg_ustc_team_dividend = ustc_dividend.multiply_ratio(team.ustc_share_percent, MAX_BPS) / Uint128::from(team_fires);
+                    ustc_team_dividend = avg_ustc_team_dividend * Uint128::from(team_fires);
+                }
+            }
+        }
+        let left_ustc_dividend = ustc_dividend - ustc_team_dividend;
+        let avg_ustc_dividend = left_ustc_dividend / Uint128::from(total_fires);
+        Ok((avg_ustc_dividend, avg_ustc_team_dividend, left_ustc_dividend - avg_ustc_dividend * Uint128::from(total_fires)))
+    }
+
     fn validate_lunc_func(
         &self,
         info: MessageInfo,
@@ -391,6 +1425,30 @@ impl<'a> FomoDFCState<'a> {
         deps: Deps,
     ) -> StdResult<GetConfigResponse> {
         let config = CONFIG.load(deps.storage)?;
+        self.config_response(deps, config)
+    }
+
+    pub fn query_pending_config(&self, deps: Deps) -> StdResult<GetPendingConfigResponse> {
+        let current_cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
+        let (config, effective_cycle) = match PENDING_CONFIG.may_load(deps.storage)? {
+            Some(config) => (Some(self.config_response(deps, config)?), Some(current_cycle + 1)),
+            None => (None, None),
+        };
+        Ok(GetPendingConfigResponse {
+            active_config: self.query_config(deps)?,
+            pending_config: config,
+            effective_cycle,
+        })
+    }
+
+    pub fn query_owner(&self, deps: Deps) -> StdResult<GetOwnerResponse> {
+        Ok(GetOwnerResponse {
+            owner: self.owner.may_load(deps.storage)?.map(|owner| owner.to_string()).unwrap_or_default(),
+            pending_owner: self.pending_owner.may_load(deps.storage)?.map(|owner| owner.to_string()),
+        })
+    }
+
+    fn config_response(&self, deps: Deps, config: Config) -> StdResult<GetConfigResponse> {
         Ok(GetConfigResponse { 
             dfc_address: deps.api.addr_humanize(&config.dfc_address)?.to_string(),
             dflunc_address: deps.api.addr_humanize(&config.dflunc_address)?.to_string(),
@@ -399,12 +1457,23 @@ impl<'a> FomoDFCState<'a> {
             max_delay_time: config.max_delay_time,  // 24 hour
             delay_time_per_burn: config.delay_time_per_burn,  // 1 minute
             initial_lunc_amount_in: config.initial_lunc_amount_in,  // 10000 lunc
+            fire_dfc_amount: config.fire_dfc_amount,  // 1 dfc
+            price_step: config.price_step,
             dividend_percent: config.dividend_percent,  // 70%
             burned_percent: config.burned_percent,      // 13%
             invite_percent: config.invite_percent,    // 12%
+            second_invite_percent: config.second_invite_percent,
             dev_percent: config.dev_percent,       // 5%
-            ustc_last_fire_numerator: config.ustc_last_fire_numerator,      // 2
-            ustc_last_fire_denominator: config.ustc_last_fire_denominator,    // 3
+            unused_invite_destination: config.unused_invite_destination,
+            keeper_tip_percent: config.keeper_tip_percent,
+            jackpot_carry_percent: config.jackpot_carry_percent,
+            timer_policy: config.timer_policy,
+            claim_window: config.claim_window,
+            prize_table: config.prize_table,
+            governance_address: match config.governance_address {
+                Some(governance_address) => deps.api.addr_humanize(&governance_address)?.to_string(),
+                None => String::from(""),
+            },
         })
     }
     
@@ -437,6 +1506,349 @@ impl<'a> FomoDFCState<'a> {
         })
     }
     
+    pub fn query_price_curve(&self, deps: Deps, env: Env, count: u32) -> StdResult<GetPriceCurveResponse> {
+        let current_time = env.block.time.seconds();
+        let (cycle, mut lunc_amount_in, config) = self.next_fire(deps, current_time)?;
+        let end_time = self.end_time.may_load(deps.storage)?.unwrap_or(0);
+        let in_final_window = current_time <= end_time && end_time - current_time <= config.timer_policy.final_seconds;
+
+        let mut lunc_amounts_in = vec![];
+        for _ in 0..count.min(MAX_PRICE_CURVE_LENGTH) {
+            lunc_amounts_in.push(lunc_amount_in);
+            lunc_amount_in = self.next_fire_price(lunc_amount_in, &config, in_final_window)?;
+        }
+        Ok(GetPriceCurveResponse {
+            cycle,
+            fire_dfc_amount: config.fire_dfc_amount,
+            lunc_amounts_in,
+        })
+    }
+
+    pub fn query_round_status(&self, deps: Deps, env: Env) -> StdResult<GetRoundStatusResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        let current_time = env.block.time.seconds();
+        let cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
+        let end_time = self.end_time.may_load(deps.storage)?.unwrap_or(0);
+        let total_fires = self.cycle_total_fires.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(0);
+        let last_burner = self.cycle_last_burner.may_load(deps.storage, U64Key::from(cycle))?;
+        let (_, next_fire_price, _) = self.next_fire(deps, current_time)?;
+
+        // what settling now would pay: a burn settles without the keeper tip, SettleRound takes it off the pot
+        let ustc_jackpot_carry = self.ustc_jackpot_carry.may_load(deps.storage)?.unwrap_or(Uint128::zero());
+        let ustc_pot = self.reserved_ustc(&deps.querier, deps.api, &config)? + ustc_jackpot_carry;
+        let keeper_tip = ustc_pot.multiply_ratio(config.keeper_tip_percent, MAX_BPS);
+        let (projected_last_burner_prize, projected_ustc_dividend_per_fire) =
+            self.projected_payouts(deps, &config, cycle, total_fires, ustc_pot)?;
+        let (projected_last_burner_prize_after_keeper_tip, projected_ustc_dividend_per_fire_after_keeper_tip) =
+            self.projected_payouts(deps, &config, cycle, total_fires, ustc_pot - keeper_tip)?;
+
+        Ok(GetRoundStatusResponse {
+            cycle,
+            end_time,
+            seconds_remaining: end_time.saturating_sub(current_time),
+            ended: current_time > end_time && total_fires > 0,
+            total_fires,
+            next_fire_price,
+            last_burner: last_burner.map(|burner| burner.to_string()).unwrap_or_default(),
+            ustc_pot,
+            projected_last_burner_prize,
+            projected_ustc_dividend_per_fire,
+            projected_keeper_tip: keeper_tip,
+            projected_last_burner_prize_after_keeper_tip,
+            projected_ustc_dividend_per_fire_after_keeper_tip,
+        })
+    }
+
+    // the last burner prize and the dividend per fire paid out of the given pot, after the jackpot carry
+    fn projected_payouts(
+        &self,
+        deps: Deps,
+        config: &Config,
+        cycle: u64,
+        total_fires: u64,
+        ustc_pot: Uint128,
+    ) -> StdResult<(Uint128, Uint128)> {
+        if total_fires == 0 {
+            return Ok((Uint128::zero(), Uint128::zero()));
+        }
+        let ustc_to_distribute = ustc_pot - ustc_pot.multiply_ratio(config.jackpot_carry_percent, MAX_BPS);
+        let mut last_burner_prize = Uint128::zero();
+        let mut ustc_amount_to_winners = Uint128::zero();
+        for (_, tier, percent) in self.cycle_winners(deps.storage, cycle, &config.prize_table)? {
+            let amount = ustc_to_distribute.multiply_ratio(percent, MAX_BPS);
+            if tier == PrizeTier::LastBurner {
+                last_burner_prize = amount;
+            }
+            ustc_amount_to_winners += amount;
+        }
+        let ustc_dividend_carry = self.ustc_dividend_carry.may_load(deps.storage)?.unwrap_or(Uint128::zero());
+        let (avg_ustc_dividend, _, _) = self.split_ustc_dividend(deps.storage, cycle, total_fires, ustc_to_distribute - ustc_amount_to_winners + ustc_dividend_carry)?;
+        Ok((last_burner_prize, avg_ustc_dividend))
+    }
+
+    // the cycle and price of the next fire, with the config it will be sold under
+    fn next_fire(&self, deps: Deps, current_time: u64) -> StdResult<(u64, Uint128, Config)> {
+        let config = CONFIG.load(deps.storage)?;
+        let cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
+        let end_time = self.end_time.may_load(deps.storage)?.unwrap_or(0);
+        let total_fires = self.cycle_total_fires.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(0);
+
+        // the next fire after the timer ran out opens a new cycle at the initial price
+        if current_time > end_time && total_fires > 0 {
+            let config = PENDING_CONFIG.may_load(deps.storage)?.unwrap_or(config);
+            return Ok((cycle + 1, config.initial_lunc_amount_in, config));
+        }
+        let lunc_amount_in = self.lunc_amount_in_required.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(config.initial_lunc_amount_in);
+        Ok((cycle, lunc_amount_in, config))
+    }
+
+    pub fn query_claimable(
+        &self,
+        deps: Deps,
+        user_address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<GetClaimableResponse> {
+        let address = deps.api.addr_validate(user_address.as_str())?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|cycle| Bound::exclusive(U64Key::from(cycle)));
+        let current_cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
+
+        // the limit bounds the cycles scanned, expired and fully claimed ones included
+        let mut cycles = vec![];
+        let mut last_scanned = None;
+        let mut scanned = self.user_cycle_fires.prefix(address.clone()).keys(deps.storage, start, None, Order::Ascending);
+        for key in scanned.by_ref().take(limit) {
+            let cycle = u64_from_key(&key);
+            last_scanned = Some(cycle);
+            if self.is_expired(deps.storage, cycle, current_cycle)? {
+                continue;
+            }
+            let (lunc_amount, ustc_amount) = self.cycle_claimable(deps.storage, &address, cycle, current_cycle)?;
+            if lunc_amount.is_zero() && ustc_amount.is_zero() {
+                continue;
+            }
+            cycles.push(ClaimableCycle { cycle, lunc_amount, ustc_amount });
+        }
+        let next_start_after = match scanned.next() {
+            Some(_) => last_scanned,
+            None => None,
+        };
+
+        Ok(GetClaimableResponse {
+            total_lunc_amount: cycles.iter().map(|c| c.lunc_amount).sum(),
+            total_ustc_amount: cycles.iter().map(|c| c.ustc_amount).sum(),
+            cycles,
+            next_start_after,
+        })
+    }
+
+    pub fn query_cycle_prizes(&self, deps: Deps, cycle: u64) -> StdResult<GetCyclePrizesResponse> {
+        let prizes = self.cycle_prizes.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or_default();
+        Ok(GetCyclePrizesResponse {
+            cycle,
+            prizes: prizes
+                .into_iter()
+                .map(|prize| PrizeInfo {
+                    winner: prize.winner.to_string(),
+                    tier: prize.tier,
+                    amount: prize.amount,
+                })
+                .collect(),
+        })
+    }
+
+    pub fn query_referral_info(&self, deps: Deps, user_address: String) -> StdResult<GetReferralInfoResponse> {
+        let address = deps.api.addr_validate(user_address.as_str())?;
+        let inviter = self.user_invited_address.may_load(deps.storage, address.clone())?;
+        Ok(GetReferralInfoResponse {
+            inviter: inviter.map(|inviter| inviter.to_string()),
+            invitee_count: self.user_invitee_count.may_load(deps.storage, address.clone())?.unwrap_or(0),
+            lifetime_earnings: self.user_referral_earnings.may_load(deps.storage, address.clone())?.unwrap_or(Uint128::zero()),
+            pending_rewards: self.pending_referral_rewards.may_load(deps.storage, address)?.unwrap_or(Uint128::zero()),
+        })
+    }
+
+    pub fn query_dividend_carry(&self, deps: Deps) -> StdResult<GetDividendCarryResponse> {
+        Ok(GetDividendCarryResponse {
+            lunc_amount: self.lunc_dividend_carry.may_load(deps.storage)?.unwrap_or(Uint128::zero()),
+            ustc_amount: self.ustc_dividend_carry.may_load(deps.storage)?.unwrap_or(Uint128::zero()),
+        })
+    }
+
+    pub fn query_lunc_deposit(&self, deps: Deps, user_address: String) -> StdResult<GetLuncDepositResponse> {
+        let address = deps.api.addr_validate(user_address.as_str())?;
+        Ok(GetLuncDepositResponse {
+            amount: self.user_lunc_deposit.may_load(deps.storage, address)?.unwrap_or(Uint128::zero()),
+        })
+    }
+
+    pub fn query_pending_dev_rewards(&self, deps: Deps) -> StdResult<GetPendingDevRewardsResponse> {
+        Ok(GetPendingDevRewardsResponse {
+            amount: self.pending_dev_rewards.may_load(deps.storage)?.unwrap_or(Uint128::zero()),
+        })
+    }
+
+    pub fn query_invitees(
+        &self,
+        deps: Deps,
+        user_address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<GetInviteesResponse> {
+        let address = deps.api.addr_validate(user_address.as_str())?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+        let invitees = self.user_invitees
+            .prefix(address)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| Ok(String::from_utf8(item?.0)?))
+            .collect::<StdResult<Vec<String>>>()?;
+        Ok(GetInviteesResponse { invitees })
+    }
+
+    pub fn query_rounds(&self, deps: Deps, start_after: Option<u64>, limit: Option<u32>) -> StdResult<GetRoundsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|cycle| Bound::exclusive(U64Key::from(cycle)));
+        let current_cycle = self.current_cycle.may_load(deps.storage)?.unwrap_or(0);
+
+        let rounds = self.cycle_total_fires
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (key, total_fires) = item?;
+                let cycle = u64_from_key(&key);
+                let last_burner = self.cycle_last_burner.may_load(deps.storage, U64Key::from(cycle))?;
+                Ok(RoundSummary {
+                    cycle,
+                    settled: cycle < current_cycle,
+                    total_fires,
+                    total_burned: self.cycle_total_burned.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero()),
+                    total_dividend: self.cycle_total_dividend.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero()),
+                    avg_ustc_dividend: self.cycle_avg_ustc_dividend.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero()),
+                    last_burner: last_burner.map(|burner| burner.to_string()).unwrap_or_default(),
+                    last_burner_prize: self.cycle_last_burner_rewards.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero()),
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(GetRoundsResponse { rounds })
+    }
+
+    pub fn query_round_participants(
+        &self,
+        deps: Deps,
+        cycle: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<GetRoundParticipantsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let participants = self.cycle_user_fires
+            .prefix(U64Key::from(cycle))
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (key, fires) = item?;
+                Ok(RoundParticipant {
+                    user_address: String::from_utf8(key)?,
+                    fires,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(GetRoundParticipantsResponse { cycle, participants })
+    }
+
+    pub fn query_jackpot(&self, deps: Deps) -> StdResult<GetJackpotResponse> {
+        let config = CONFIG.load(deps.storage)?;
+        Ok(GetJackpotResponse {
+            ustc_jackpot_carry: self.ustc_jackpot_carry.may_load(deps.storage)?.unwrap_or(Uint128::zero()),
+            jackpot_carry_percent: config.jackpot_carry_percent,
+        })
+    }
+
+    pub fn query_teams(&self, deps: Deps, cycle: Option<u64>) -> StdResult<GetTeamsResponse> {
+        let cycle = match cycle {
+            Some(cycle) => cycle,
+            None => self.current_cycle.may_load(deps.storage)?.unwrap_or(0),
+        };
+        let teams = self.teams
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (key, team) = item?;
+                let team_id = u64_from_key(&key);
+                let fires = self.cycle_team_fires.may_load(deps.storage, (U64Key::from(cycle), U64Key::from(team_id)))?.unwrap_or(0);
+                Ok(TeamInfo { team_id, team, fires })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(GetTeamsResponse {
+            cycle,
+            teams,
+            leading_team: self.cycle_last_team.may_load(deps.storage, U64Key::from(cycle))?,
+            lunc_pot: self.cycle_lunc_pot.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero()),
+            avg_ustc_team_dividend: self.cycle_avg_ustc_team_dividend.may_load(deps.storage, U64Key::from(cycle))?.unwrap_or(Uint128::zero()),
+        })
+    }
+
+    pub fn query_player_stats(&self, deps: Deps, address: String) -> StdResult<GetPlayerStatsResponse> {
+        let address = deps.api.addr_validate(address.as_str())?;
+        let stats = self.player_stats.may_load(deps.storage, address.clone())?.unwrap_or_default();
+        Ok(GetPlayerStatsResponse {
+            address: address.to_string(),
+            total_fires: stats.total_fires,
+            lunc_spent: stats.lunc_spent,
+            lunc_dividends_claimed: stats.lunc_dividends_claimed,
+            ustc_dividends_claimed: stats.ustc_dividends_claimed,
+            rounds_won: stats.rounds_won,
+            ustc_prizes_won: stats.ustc_prizes_won,
+            referral_income: self.user_referral_earnings.may_load(deps.storage, address)?.unwrap_or(Uint128::zero()),
+        })
+    }
+
+    // players by claimed dividends of the denom, highest first
+    pub fn query_top_players(
+        &self,
+        deps: Deps,
+        denom: Option<DividendDenom>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<GetTopPlayersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let denom = denom.unwrap_or(DividendDenom::Lunc);
+        let ranking = match denom {
+            DividendDenom::Lunc => &self.lunc_dividend_ranking,
+            DividendDenom::Ustc => &self.ustc_dividend_ranking,
+        };
+        let end = match start_after {
+            Some(start_after) => {
+                let address = deps.api.addr_validate(start_after.as_str())?;
+                let stats = self.player_stats.may_load(deps.storage, address.clone())?.unwrap_or_default();
+                let amount = match denom {
+                    DividendDenom::Lunc => stats.lunc_dividends_claimed,
+                    DividendDenom::Ustc => stats.ustc_dividends_claimed,
+                };
+                Some(Bound::exclusive((U128Key::from(amount.u128()), address).joined_key()))
+            }
+            None => None,
+        };
+
+        let players = ranking
+            .range(deps.storage, None, end, Order::Descending)
+            .take(limit)
+            .map(|item| {
+                let (_, address) = item?;
+                let stats = self.player_stats.may_load(deps.storage, address.clone())?.unwrap_or_default();
+                Ok(TopPlayer {
+                    address: address.to_string(),
+                    lunc_dividends_claimed: stats.lunc_dividends_claimed,
+                    ustc_dividends_claimed: stats.ustc_dividends_claimed,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(GetTopPlayersResponse { players })
+    }
+
     pub fn query_user_info(&self, deps: Deps, user_addr: String, cycle: u64) -> StdResult<GetUserInfoResponse> {
         let address = deps.api.addr_validate(user_addr.as_str())?;
         let user_cycle_fires = self.user_cycle_fires.may_load(deps.storage,
@@ -458,3 +1870,135 @@ impl<'a> FomoDFCState<'a> {
         })
     }
 }
+
+fn u64_from_key(key: &[u8]) -> u64 {
+    u64::from_be_bytes(key.try_into().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::{ExecuteMsg, InstantiateMsg};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{coins, Timestamp};
+
+    const START_TIME: u64 = 1_000_000;
+
+    fn instantiate_with_timer(deps: DepsMut, timer_policy: TimerPolicy) {
+        let msg = InstantiateMsg {
+            dfc_address: "dfc".to_string(),
+            dflunc_address: "dflunc".to_string(),
+            dev_address: "dev".to_string(),
+            burned_address: "burned".to_string(),
+            max_delay_time: 1000,
+            delay_time_per_burn: 100,
+            initial_lunc_amount_in: Uint128::from(1000u128),
+            fire_dfc_amount: Uint128::from(1u128),
+            price_step: PriceStep::Linear { increment: Uint128::from(1u128) },
+            dividend_percent: MAX_BPS,
+            burned_percent: 0,
+            invite_percent: 0,
+            second_invite_percent: None,
+            dev_percent: 0,
+            unused_invite_destination: UnusedInviteDestination::Dividend,
+            prize_table: PrizeTable {
+                last_burner_percent: 0,
+                previous_burner_percents: vec![],
+                top_burner_percent: 0,
+            },
+            keeper_tip_percent: None,
+            jackpot_carry_percent: None,
+            timer_policy: Some(timer_policy),
+            claim_window: None,
+            governance_address: None,
+        };
+        FomoDFCState::default().instantiate(deps, mock_env(), mock_info("owner", &[]), msg).unwrap();
+    }
+
+    // burns one fire of the first round at the given time, paying the current price
+    fn burn_at(deps: DepsMut, time: u64) {
+        let state = FomoDFCState::default();
+        let price = state.lunc_amount_in_required.load(deps.storage, U64Key::from(0)).unwrap();
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(time);
+        let msg = ExecuteMsg::Burn { invite_address: None, fires: 1, team: None };
+        state.execute(deps, env, mock_info("burner", &coins(price.u128(), LUNC_DENOM)), msg).unwrap();
+    }
+
+    #[test]
+    fn extension_shrinks_as_the_round_ages() {
+        let mut deps = mock_dependencies(&[]);
+        instantiate_with_timer(deps.as_mut(), TimerPolicy {
+            age_step: Some(60),
+            age_decay_percent: 10000,
+            min_delay_time_per_burn: 50,
+            ..TimerPolicy::default()
+        });
+        let state = FomoDFCState::default();
+        burn_at(deps.as_mut(), START_TIME);
+        assert_eq!(state.end_time.load(&deps.storage).unwrap(), START_TIME + 1000);
+
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(state.delay_time_per_fire(deps.as_ref(), &config, 0, START_TIME).unwrap(), 100);
+        assert_eq!(state.delay_time_per_fire(deps.as_ref(), &config, 0, START_TIME + 119).unwrap(), 90);
+        assert_eq!(state.delay_time_per_fire(deps.as_ref(), &config, 0, START_TIME + 120).unwrap(), 80);
+        // never below the minimum extension
+        assert_eq!(state.delay_time_per_fire(deps.as_ref(), &config, 0, START_TIME + 900).unwrap(), 50);
+
+        // two full age steps take 20% off the extension of the fire
+        burn_at(deps.as_mut(), START_TIME + 120);
+        assert_eq!(state.end_time.load(&deps.storage).unwrap(), START_TIME + 1080);
+    }
+
+    #[test]
+    fn max_round_length_caps_the_timer() {
+        let mut deps = mock_dependencies(&[]);
+        instantiate_with_timer(deps.as_mut(), TimerPolicy {
+            max_round_length: Some(1050),
+            ..TimerPolicy::default()
+        });
+        let state = FomoDFCState::default();
+        burn_at(deps.as_mut(), START_TIME);
+        assert_eq!(state.end_time.load(&deps.storage).unwrap(), START_TIME + 1000);
+
+        burn_at(deps.as_mut(), START_TIME + 60);
+        assert_eq!(state.end_time.load(&deps.storage).unwrap(), START_TIME + 1050);
+        burn_at(deps.as_mut(), START_TIME + 500);
+        assert_eq!(state.end_time.load(&deps.storage).unwrap(), START_TIME + 1050);
+    }
+
+    #[test]
+    fn unbounded_max_round_length_does_not_overflow() {
+        let mut deps = mock_dependencies(&[]);
+        instantiate_with_timer(deps.as_mut(), TimerPolicy {
+            max_round_length: Some(u64::MAX),
+            ..TimerPolicy::default()
+        });
+        let state = FomoDFCState::default();
+        burn_at(deps.as_mut(), START_TIME);
+        burn_at(deps.as_mut(), START_TIME + 60);
+        assert_eq!(state.end_time.load(&deps.storage).unwrap(), START_TIME + 1060);
+    }
+
+    #[test]
+    fn price_rises_faster_in_the_final_window() {
+        let mut deps = mock_dependencies(&[]);
+        instantiate_with_timer(deps.as_mut(), TimerPolicy {
+            final_seconds: 50,
+            final_min_price_increase_percent: 10000,
+            ..TimerPolicy::default()
+        });
+        let state = FomoDFCState::default();
+        burn_at(deps.as_mut(), START_TIME);
+        assert_eq!(state.lunc_amount_in_required.load(&deps.storage, U64Key::from(0)).unwrap(), Uint128::from(1001u128));
+
+        // 60 seconds left is outside the final window
+        burn_at(deps.as_mut(), START_TIME + 940);
+        assert_eq!(state.end_time.load(&deps.storage).unwrap(), START_TIME + 1100);
+        assert_eq!(state.lunc_amount_in_required.load(&deps.storage, U64Key::from(0)).unwrap(), Uint128::from(1002u128));
+
+        // 40 seconds left, 10% of 1002 beats the linear step of 1
+        burn_at(deps.as_mut(), START_TIME + 1060);
+        assert_eq!(state.lunc_amount_in_required.load(&deps.storage, U64Key::from(0)).unwrap(), Uint128::from(1102u128));
+    }
+}
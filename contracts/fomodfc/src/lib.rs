@@ -4,8 +4,14 @@ mod error;
 pub mod state;
 pub mod handler;
 
+#[cfg(test)]
+mod mock_querier;
+#[cfg(test)]
+mod testing;
+
 pub use crate::error::ContractError;
 pub use crate::state::FomoDFCState;
+#[cfg(not(feature = "library"))]
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 #[cfg(not(feature = "library"))]
@@ -22,7 +28,7 @@ pub mod entry {
         env: Env,
         info: MessageInfo,
         msg: InstantiateMsg,
-    ) -> StdResult<Response> {
+    ) -> Result<Response, ContractError> {
         let tract = FomoDFCState::default();
         tract.instantiate(deps, env, info, msg)
     }
@@ -45,7 +51,8 @@ pub mod entry {
     }
 
     #[entry_point]
-    pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-        Ok(Response::default())
+    pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response> {
+        let tract = FomoDFCState::default();
+        tract.migrate(deps, env, msg)
     }
 }
\ No newline at end of file
@@ -0,0 +1,86 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, ContractResult, Decimal, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
+
+use dflunc::msg::{GetBaseStateResponse, QueryMsg as DfluncQueryMsg};
+
+/// mock_dependencies answering the dflunc base state and the terra tax queries, ustc is sent untaxed
+pub fn mock_dependencies() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: WasmMockQuerier::new(MockQuerier::new(&[])),
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<TerraQueryWrapper>,
+    // protocol fees dflunc reserved for the round and not claimed yet
+    reserved_ustc: Uint128,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+        WasmMockQuerier {
+            base,
+            reserved_ustc: Uint128::zero(),
+        }
+    }
+
+    pub fn with_reserved_ustc(&mut self, reserved_ustc: u128) {
+        self.reserved_ustc = Uint128::from(reserved_ustc);
+    }
+
+    pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+        match request {
+            QueryRequest::Custom(TerraQueryWrapper { route: TerraRoute::Treasury, query_data }) => {
+                let response = match query_data {
+                    TerraQuery::TaxRate {} => to_binary(&TaxRateResponse { rate: Decimal::zero() }),
+                    TerraQuery::TaxCap { .. } => to_binary(&TaxCapResponse { cap: Uint128::zero() }),
+                    _ => panic!("DO NOT ENTER HERE"),
+                };
+                SystemResult::Ok(ContractResult::from(response))
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. }) => {
+                let response = match from_binary(msg).unwrap() {
+                    DfluncQueryMsg::GetBaseState {} => to_binary(&GetBaseStateResponse {
+                        current_block_time: 0,
+                        total_number_of_batches: 0,
+                        current_cycle: 0,
+                        current_started_cycle: 0,
+                        previous_started_cycle: 0,
+                        last_started_cycle: 0,
+                        pending_fees: Uint128::zero(),
+                        pending_stake: Uint128::zero(),
+                        pending_stake_withdrawal: Uint128::zero(),
+                        current_cycle_reward: Uint128::zero(),
+                        last_cycle_reward: Uint128::zero(),
+                        total_protocol_fees_reserved: self.reserved_ustc,
+                        withdrawed_protocol_fees_reserved: Uint128::zero(),
+                    }),
+                    _ => panic!("DO NOT ENTER HERE"),
+                };
+                SystemResult::Ok(ContractResult::from(response))
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
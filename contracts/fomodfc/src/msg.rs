@@ -1,4 +1,5 @@
 use cosmwasm_std::Uint128;
+use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -11,32 +12,191 @@ pub struct InstantiateMsg {
     pub max_delay_time: u64,
     pub delay_time_per_burn: u64,
     pub initial_lunc_amount_in: Uint128,
+    pub fire_dfc_amount: Uint128,
+    pub price_step: PriceStep,
     pub dividend_percent: u64,
     pub burned_percent: u64,
     pub invite_percent: u64,
+    // paid to the inviter of the inviter
+    pub second_invite_percent: Option<u64>,
     pub dev_percent: u64,
-    pub ustc_last_fire_numerator: u64,
-    pub ustc_last_fire_denominator: u64,
+    pub unused_invite_destination: UnusedInviteDestination,
+    pub prize_table: PrizeTable,
+    // share of the settled ustc paid to whoever calls SettleRound
+    pub keeper_tip_percent: Option<u64>,
+    // share of the settled ustc rolled into the pot of the next round
+    pub jackpot_carry_percent: Option<u64>,
+    pub timer_policy: Option<TimerPolicy>,
+    // rounds a settled round's dividends stay claimable, forever when not set
+    pub claim_window: Option<u64>,
+    pub governance_address: Option<String>,
+}
+
+// rules on top of delay_time_per_burn and max_delay_time, the default keeps the flat extension
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct TimerPolicy {
+    // every full pot_step of the ustc pot takes pot_decay_percent off the extension per fire
+    pub pot_step: Option<Uint128>,
+    pub pot_decay_percent: u64,
+    // every full age_step seconds since the round opened takes age_decay_percent off the extension per fire
+    pub age_step: Option<u64>,
+    pub age_decay_percent: u64,
+    // the extension per fire doesn't shrink below this
+    pub min_delay_time_per_burn: u64,
+    // seconds from the opening fire after which the timer can't run
+    pub max_round_length: Option<u64>,
+    // fires bought in the last final_seconds of the timer raise the price by at least this percent
+    pub final_seconds: u64,
+    pub final_min_price_increase_percent: u64,
+}
+
+// how the LUNC price of a fire grows after each fire in a cycle
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceStep {
+    // price + increment
+    Linear { increment: Uint128 },
+    // price + price * rate / 100000
+    Exponential { rate: u64 },
+}
+
+// shares of the settled ustc, the rest is split evenly among the fires of the round
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PrizeTable {
+    pub last_burner_percent: u64,
+    // for the burners before the last one, most recent first, in descending order
+    pub previous_burner_percents: Vec<u64>,
+    // the burner with the most fires in the round
+    pub top_burner_percent: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PrizeTier {
+    LastBurner,
+    PreviousBurner { rank: u64 },
+    TopBurner,
+}
+
+// denom the top players are ranked by
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DividendDenom {
+    Lunc,
+    Ustc,
+}
+
+// where the invite share goes when a burn has no valid inviter
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnusedInviteDestination {
+    Dividend,
+    Burn,
+    Dev,
+}
+
+// a team picked on burn, its percents split the dividend and burn share of its fires
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Team {
+    pub name: String,
+    pub dividend_percent: u64,
+    pub burned_percent: u64,
+    // goes to the lunc pot of the round, won by the last burner
+    pub pot_percent: u64,
+    // share of the settled ustc left after prizes, paid to the fires of the team when it wins the round
+    pub ustc_share_percent: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
+    // lunc sent above the price of the fires is credited to the burner's lunc deposit
     Burn {
         invite_address: Option<String>,
+        fires: u64,
+        team: Option<u64>,
     },
+    // burns with dfc sent through the token, the fires follow from the amount sent
+    Receive(Cw20ReceiveMsg),
+    // lunc used by the next burn through Receive, sent in the same transaction
+    DepositLunc {},
+    // withdraws burn overpayments, won lunc pots and unused deposits
+    WithdrawLuncDeposit {},
     ClaimLuncDividend {
         cycle: u64,
     },
     ClaimUstcDividend {
         cycle: u64,
     },
+    // claims the given cycles, or the next cycles the sender took part in
+    ClaimAllDividends {
+        cycles: Option<Vec<u64>>,
+        limit: Option<u32>,
+    },
+    WithdrawReferralRewards {},
+    // sends the accrued dev rewards to the dev address
+    WithdrawDevRewards {},
+    // the new owner has to accept the ownership
+    TransferOwnership {
+        owner: String,
+    },
+    AcceptOwnership {},
+    // anyone can settle a round once its timer ran out
+    SettleRound {},
+    UpdateConfig(Box<UpdateConfigMsg>),
+    // adds or replaces a team, fires already bought keep their split
+    SetTeam {
+        team_id: u64,
+        team: Team,
+    },
+    RemoveTeam {
+        team_id: u64,
+    },
+    // anyone can move the unclaimed dividends of an expired cycle into the current round's pot
+    SweepExpired {
+        cycle: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    Burn {
+        invite_address: Option<String>,
+        team: Option<u64>,
+    },
+}
+
+// fields left as None keep their current value
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct UpdateConfigMsg {
+    pub max_delay_time: Option<u64>,
+    pub delay_time_per_burn: Option<u64>,
+    pub dividend_percent: Option<u64>,
+    pub burned_percent: Option<u64>,
+    pub invite_percent: Option<u64>,
+    pub dev_percent: Option<u64>,
+    pub second_invite_percent: Option<u64>,
+    pub unused_invite_destination: Option<UnusedInviteDestination>,
+    pub keeper_tip_percent: Option<u64>,
+    pub jackpot_carry_percent: Option<u64>,
+    pub timer_policy: Option<TimerPolicy>,
+    pub claim_window: Option<u64>,
+    // rounds settled from then on stay claimable forever
+    pub clear_claim_window: Option<bool>,
+    pub prize_table: Option<PrizeTable>,
+    pub dev_address: Option<String>,
+    pub dflunc_address: Option<String>,
+    pub governance_address: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     GetConfig {},
+    // the active config and the one taking effect from the next round
+    GetPendingConfig {},
+    GetOwner {},
     GetCycleInfo {
         cycle: u64,
     },
@@ -44,6 +204,57 @@ pub enum QueryMsg {
         user_address: String,
         cycle: u64,
     },
+    GetRoundStatus {},
+    // ustc carried over from the last settled round into the current pot
+    GetJackpot {},
+    // the teams with their fires in the cycle, the current one by default
+    GetTeams {
+        cycle: Option<u64>,
+    },
+    GetPlayerStats {
+        address: String,
+    },
+    // ranked by claimed lunc dividends by default
+    GetTopPlayers {
+        denom: Option<DividendDenom>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    GetRounds {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    GetRoundParticipants {
+        cycle: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    GetPriceCurve {
+        count: u32,
+    },
+    GetCyclePrizes {
+        cycle: u64,
+    },
+    GetReferralInfo {
+        user_address: String,
+    },
+    GetPendingDevRewards {},
+    GetDividendCarry {},
+    GetLuncDeposit {
+        user_address: String,
+    },
+    GetInvitees {
+        user_address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // cycles with unclaimed dividends, starting after the given cycle
+    // the limit counts the cycles scanned, so a page can list fewer cycles and still have a next one
+    GetClaimable {
+        user_address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 // We define a custom struct for each query response
@@ -56,12 +267,33 @@ pub struct GetConfigResponse {
     pub max_delay_time: u64,  // 24 hour
     pub delay_time_per_burn: u64,  // 1 minute
     pub initial_lunc_amount_in: Uint128,  // 10000 lunc
+    pub fire_dfc_amount: Uint128,  // 1 dfc
+    pub price_step: PriceStep,
     pub dividend_percent: u64,  // 70%
     pub burned_percent: u64,      // 13%
     pub invite_percent: u64,    // 12%
+    pub second_invite_percent: u64,
     pub dev_percent: u64,       // 5%
-    pub ustc_last_fire_numerator: u64,      // 2
-    pub ustc_last_fire_denominator: u64,    // 3
+    pub unused_invite_destination: UnusedInviteDestination,
+    pub prize_table: PrizeTable,
+    pub keeper_tip_percent: u64,
+    pub jackpot_carry_percent: u64,
+    pub timer_policy: TimerPolicy,
+    pub claim_window: Option<u64>,
+    pub governance_address: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetPendingConfigResponse {
+    pub active_config: GetConfigResponse,
+    pub pending_config: Option<GetConfigResponse>,
+    pub effective_cycle: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetOwnerResponse {
+    pub owner: String,
+    pub pending_owner: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -102,6 +334,173 @@ pub struct GetUserInfoResponse {
     pub user_burned_at_least_once: bool,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetRoundStatusResponse {
+    pub cycle: u64,
+    pub end_time: u64,
+    pub seconds_remaining: u64,
+    // the timer ran out and the round waits for settlement
+    pub ended: bool,
+    pub total_fires: u64,
+    pub next_fire_price: Uint128,
+    pub last_burner: String,
+    // ustc reserved in dflunc for the round
+    pub ustc_pot: Uint128,
+    // payouts if the round were settled now by the next burn
+    pub projected_last_burner_prize: Uint128,
+    pub projected_ustc_dividend_per_fire: Uint128,
+    // payouts if the round were settled now through SettleRound, which first pays the keeper tip
+    pub projected_keeper_tip: Uint128,
+    pub projected_last_burner_prize_after_keeper_tip: Uint128,
+    pub projected_ustc_dividend_per_fire_after_keeper_tip: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetJackpotResponse {
+    pub ustc_jackpot_carry: Uint128,
+    pub jackpot_carry_percent: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TeamInfo {
+    pub team_id: u64,
+    pub team: Team,
+    pub fires: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetTeamsResponse {
+    pub cycle: u64,
+    pub teams: Vec<TeamInfo>,
+    // team of the last burn, the winning team once the cycle is settled
+    pub leading_team: Option<u64>,
+    pub lunc_pot: Uint128,
+    pub avg_ustc_team_dividend: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetPlayerStatsResponse {
+    pub address: String,
+    pub total_fires: u64,
+    pub lunc_spent: Uint128,
+    pub lunc_dividends_claimed: Uint128,
+    pub ustc_dividends_claimed: Uint128,
+    // rounds the player was the last burner of
+    pub rounds_won: u64,
+    pub ustc_prizes_won: Uint128,
+    pub referral_income: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TopPlayer {
+    pub address: String,
+    pub lunc_dividends_claimed: Uint128,
+    pub ustc_dividends_claimed: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetTopPlayersResponse {
+    pub players: Vec<TopPlayer>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundSummary {
+    pub cycle: u64,
+    pub settled: bool,
+    pub total_fires: u64,
+    pub total_burned: Uint128,
+    pub total_dividend: Uint128,
+    pub avg_ustc_dividend: Uint128,
+    pub last_burner: String,
+    pub last_burner_prize: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetRoundsResponse {
+    pub rounds: Vec<RoundSummary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundParticipant {
+    pub user_address: String,
+    pub fires: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetRoundParticipantsResponse {
+    pub cycle: u64,
+    pub participants: Vec<RoundParticipant>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetPriceCurveResponse {
+    pub cycle: u64,
+    pub fire_dfc_amount: Uint128,
+    // lunc required by each of the next fires, in order
+    pub lunc_amounts_in: Vec<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PrizeInfo {
+    pub winner: String,
+    pub tier: PrizeTier,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetCyclePrizesResponse {
+    pub cycle: u64,
+    pub prizes: Vec<PrizeInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetReferralInfoResponse {
+    pub inviter: Option<String>,
+    pub invitee_count: u64,
+    // lunc earned from level-1 and level-2 referrals
+    pub lifetime_earnings: Uint128,
+    // not withdrawn yet
+    pub pending_rewards: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetDividendCarryResponse {
+    pub lunc_amount: Uint128,
+    pub ustc_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetLuncDepositResponse {
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetPendingDevRewardsResponse {
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetInviteesResponse {
+    pub invitees: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimableCycle {
+    pub cycle: u64,
+    pub lunc_amount: Uint128,
+    pub ustc_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetClaimableResponse {
+    pub cycles: Vec<ClaimableCycle>,
+    // totals of the listed cycles
+    pub total_lunc_amount: Uint128,
+    pub total_ustc_amount: Uint128,
+    // start_after of the next page, None once every cycle of the user was scanned
+    pub next_start_after: Option<u64>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct GetWithdrawableStakeResponse {
     pub amount: Uint128,
@@ -123,4 +522,7 @@ pub struct GetUnclaimedFees {
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// Owner for contracts deployed before ownership was stored, ignored once an owner exists.
+    pub owner: Option<String>,
+}
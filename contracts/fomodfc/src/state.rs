@@ -1,13 +1,19 @@
 use cosmwasm_std::{CanonicalAddr, Uint128, Addr};
-use cw_storage_plus::{Item, Map, U64Key};
+use cw_storage_plus::{Item, Map, U128Key, U64Key};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::msg::{PriceStep, PrizeTable, PrizeTier, Team, TimerPolicy, UnusedInviteDestination};
+
 
 pub struct FomoDFCState<'a> {
+    pub owner: Item<'a, Addr>,
+    pub pending_owner: Item<'a, Addr>,
     pub end_time: Item<'a, u64>,
     pub current_cycle: Item<'a, u64>,
     pub cycle_last_burner: Map<'a, U64Key, Addr>,
+    // time of the opening fire
+    pub cycle_start_time: Map<'a, U64Key, u64>,
     pub cycle_last_burner_rewards: Map<'a, U64Key, Uint128>,
     pub cycle_total_fires: Map<'a, U64Key, u64>,
     pub cycle_total_dividend: Map<'a, U64Key, Uint128>,
@@ -16,22 +22,63 @@ pub struct FomoDFCState<'a> {
     pub cycle_avg_ustc_dividend: Map<'a, U64Key, Uint128>,
     pub lunc_amount_in_required: Map<'a, U64Key, Uint128>,
     pub cycle_ustc_claimed: Map<'a, U64Key, bool>,
+    pub cycle_recent_burners: Map<'a, U64Key, Vec<Addr>>,
+    pub cycle_top_burner: Map<'a, U64Key, (Addr, u64)>,
+    pub cycle_prizes: Map<'a, U64Key, Vec<Prize>>,
+    // dividend remainders not divisible by the fires yet
+    pub lunc_dividend_carry: Item<'a, Uint128>,
+    pub ustc_dividend_carry: Item<'a, Uint128>,
+    // part of the settled ustc kept for the pot of the next round
+    pub ustc_jackpot_carry: Item<'a, Uint128>,
 
     // user withdrawable lunc = cycle_avg_lunc_dividend * user_cycle_fires - user_cycle_dividend_withdrawed
     pub user_cycle_fires: Map<'a, (Addr, U64Key), u64>,
+    // same fires keyed by cycle first, to list the players of a cycle
+    pub cycle_user_fires: Map<'a, (U64Key, Addr), u64>,
     pub user_cycle_dividend_withdrawed: Map<'a, (Addr, U64Key), Uint128>,
     pub user_cycle_ustc_dividend_withdrawed: Map<'a, (Addr, U64Key), bool>,
+    // every cycle up to this one is finished and fully claimed by the user
+    pub user_claim_cursor: Map<'a, Addr, u64>,
+    // dividends claimed per cycle, counted from claims_tracked_from so expired cycles can be swept
+    pub cycle_lunc_dividend_claimed: Map<'a, U64Key, Uint128>,
+    pub cycle_ustc_dividend_claimed: Map<'a, U64Key, Uint128>,
+    pub claims_tracked_from: Item<'a, u64>,
+    // claim window a cycle was settled under, its dividends don't expire without one
+    pub cycle_claim_window: Map<'a, U64Key, u64>,
 
     pub user_invited_address: Map<'a, Addr, Addr>,
+    pub user_invitees: Map<'a, (Addr, Addr), bool>,
+    pub user_invitee_count: Map<'a, Addr, u64>,
+    pub user_referral_earnings: Map<'a, Addr, Uint128>,
+    pub pending_referral_rewards: Map<'a, Addr, Uint128>,
+    pub pending_dev_rewards: Item<'a, Uint128>,
+    pub user_lunc_deposit: Map<'a, Addr, Uint128>,
+    pub player_stats: Map<'a, Addr, PlayerStats>,
+    // (claimed dividends, player) to the player per denom, ranged in descending order for the top players
+    pub lunc_dividend_ranking: Map<'a, (U128Key, Addr), Addr>,
+    pub ustc_dividend_ranking: Map<'a, (U128Key, Addr), Addr>,
     pub user_burned_at_least_once: Map<'a, Addr, bool>,
+
+    pub teams: Map<'a, U64Key, Team>,
+    // (cycle, team)
+    pub cycle_team_fires: Map<'a, (U64Key, U64Key), u64>,
+    // (user, cycle, team)
+    pub user_cycle_team_fires: Map<'a, (Addr, U64Key, U64Key), u64>,
+    pub cycle_last_team: Map<'a, U64Key, u64>,
+    pub cycle_lunc_pot: Map<'a, U64Key, Uint128>,
+    // paid per fire of the winning team on top of cycle_avg_ustc_dividend
+    pub cycle_avg_ustc_team_dividend: Map<'a, U64Key, Uint128>,
 }
 
 impl Default for FomoDFCState<'static> {
     fn default() -> Self {
         Self {
+            owner: Item::new("owner"),
+            pending_owner: Item::new("pending_owner"),
             end_time: Item::new("end_time"),
             current_cycle: Item::new("current_cycle"),
             cycle_last_burner: Map::new("cycle_last_burner"),
+            cycle_start_time: Map::new("cycle_start_time"),
             cycle_last_burner_rewards: Map::new("cycle_last_burner_rewards"),
             cycle_total_fires: Map::new("cycle_total_fires"),
             cycle_total_dividend: Map::new("cycle_total_dividend"),
@@ -40,11 +87,38 @@ impl Default for FomoDFCState<'static> {
             cycle_avg_ustc_dividend: Map::new("cycle_avg_ustc_dividend"),
             lunc_amount_in_required: Map::new("lunc_amount_in_required"),
             cycle_ustc_claimed: Map::new("cycle_ustc_claimed"),
+            cycle_recent_burners: Map::new("cycle_recent_burners"),
+            cycle_top_burner: Map::new("cycle_top_burner"),
+            cycle_prizes: Map::new("cycle_prizes"),
+            lunc_dividend_carry: Item::new("lunc_dividend_carry"),
+            ustc_dividend_carry: Item::new("ustc_dividend_carry"),
+            ustc_jackpot_carry: Item::new("ustc_jackpot_carry"),
             user_cycle_fires: Map::new("user_cycle_fires"),
+            cycle_user_fires: Map::new("cycle_user_fires"),
             user_cycle_dividend_withdrawed: Map::new("user_cycle_dividend_withdrawed"),
             user_cycle_ustc_dividend_withdrawed: Map::new("user_cycle_ustc_dividend_withdrawed"),
+            user_claim_cursor: Map::new("user_claim_cursor"),
+            cycle_lunc_dividend_claimed: Map::new("cycle_lunc_dividend_claimed"),
+            cycle_ustc_dividend_claimed: Map::new("cycle_ustc_dividend_claimed"),
+            claims_tracked_from: Item::new("claims_tracked_from"),
+            cycle_claim_window: Map::new("cycle_claim_window"),
             user_invited_address: Map::new("user_invited_address"),
+            user_invitees: Map::new("user_invitees"),
+            user_invitee_count: Map::new("user_invitee_count"),
+            user_referral_earnings: Map::new("user_referral_earnings"),
+            pending_referral_rewards: Map::new("pending_referral_rewards"),
+            pending_dev_rewards: Item::new("pending_dev_rewards"),
+            user_lunc_deposit: Map::new("user_lunc_deposit"),
+            player_stats: Map::new("player_stats"),
+            lunc_dividend_ranking: Map::new("lunc_dividend_ranking"),
+            ustc_dividend_ranking: Map::new("ustc_dividend_ranking"),
             user_burned_at_least_once: Map::new("user_burned_at_least_once"),
+            teams: Map::new("teams"),
+            cycle_team_fires: Map::new("cycle_team_fires"),
+            user_cycle_team_fires: Map::new("user_cycle_team_fires"),
+            cycle_last_team: Map::new("cycle_last_team"),
+            cycle_lunc_pot: Map::new("cycle_lunc_pot"),
+            cycle_avg_ustc_team_dividend: Map::new("cycle_avg_ustc_team_dividend"),
         }
     }
 }
@@ -58,16 +132,92 @@ pub struct Config {
     pub max_delay_time: u64,  // 24 hour
     pub delay_time_per_burn: u64,  // 1 minute
     pub initial_lunc_amount_in: Uint128,  // 10000 lunc
+    // configs saved before these were configurable fall back to the original constants
+    #[serde(default = "default_fire_dfc_amount")]
+    pub fire_dfc_amount: Uint128,  // 1 dfc
+    #[serde(default = "default_price_step")]
+    pub price_step: PriceStep,  // +100 lunc per fire
     pub dividend_percent: u64,  // 70%
     pub burned_percent: u64,      // 13%
     pub invite_percent: u64,    // 12%
+    #[serde(default)]
+    pub second_invite_percent: u64,
     pub dev_percent: u64,       // 5%
-    pub ustc_last_fire_numerator: u64,      // 2
-    pub ustc_last_fire_denominator: u64,    // 3
+    #[serde(default = "default_unused_invite_destination")]
+    pub unused_invite_destination: UnusedInviteDestination,
+    #[serde(default = "default_prize_table")]
+    pub prize_table: PrizeTable,  // 2/3 to the last burner
+    // last burner share of configs saved before the prize table, turned into it by migrate
+    #[serde(default)]
+    pub ustc_last_fire_numerator: Option<u64>,
+    #[serde(default)]
+    pub ustc_last_fire_denominator: Option<u64>,
+    #[serde(default)]
+    pub keeper_tip_percent: u64,
+    #[serde(default)]
+    pub jackpot_carry_percent: u64,
+    #[serde(default)]
+    pub timer_policy: TimerPolicy,
+    #[serde(default)]
+    pub claim_window: Option<u64>,
+    pub governance_address: Option<CanonicalAddr>,
+}
+
+fn default_fire_dfc_amount() -> Uint128 {
+    Uint128::from(1000000u128)
+}
+
+fn default_price_step() -> PriceStep {
+    PriceStep::Linear { increment: Uint128::from(100000000u128) }
+}
+
+fn default_unused_invite_destination() -> UnusedInviteDestination {
+    UnusedInviteDestination::Dividend
+}
+
+impl Config {
+    // the prize table paying the legacy last burner ratio, if the config still has one
+    pub fn legacy_prize_table(&self) -> Option<PrizeTable> {
+        match (self.ustc_last_fire_numerator, self.ustc_last_fire_denominator) {
+            (Some(numerator), Some(denominator)) if denominator > 0 => Some(PrizeTable {
+                last_burner_percent: Uint128::from(100000u128).multiply_ratio(numerator, denominator).u128() as u64,
+                previous_burner_percents: vec![],
+                top_burner_percent: 0,
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn default_prize_table() -> PrizeTable {
+    PrizeTable {
+        last_burner_percent: 66666,
+        previous_burner_percents: vec![],
+        top_burner_percent: 0,
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Prize {
+    pub winner: Addr,
+    pub tier: PrizeTier,
+    pub amount: Uint128,
+}
+
+// lifetime counters of a player
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PlayerStats {
+    pub total_fires: u64,
+    pub lunc_spent: Uint128,
+    pub lunc_dividends_claimed: Uint128,
+    pub ustc_dividends_claimed: Uint128,
+    pub rounds_won: u64,
+    pub ustc_prizes_won: Uint128,
 }
 
 pub const LUNC_DENOM: &str = "uluna";
 pub const USTC_DENOM: &str = "uusd";
 
 pub const CONFIG: Item<Config> = Item::new("CONFIG");
+pub const PENDING_CONFIG: Item<Config> = Item::new("PENDING_CONFIG");
 
@@ -0,0 +1,480 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Deps, DepsMut, Env, Response, StdError, Timestamp,
+    Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::U64Key;
+
+use crate::mock_querier::mock_dependencies;
+use crate::msg::{
+    ClaimableCycle, DividendDenom, ExecuteMsg, GetClaimableResponse, GetDividendCarryResponse, GetJackpotResponse,
+    GetLuncDepositResponse, GetTopPlayersResponse, InstantiateMsg, MigrateMsg, PriceStep, PrizeTable, QueryMsg, Team,
+    TopPlayer, UnusedInviteDestination, UpdateConfigMsg,
+};
+use crate::state::FomoDFCState;
+use crate::ContractError;
+
+const LUNC_DENOM: &str = "uluna";
+const USTC_DENOM: &str = "uusd";
+const START_TIME: u64 = 1_000_000;
+const MAX_DELAY_TIME: u64 = 1000;
+
+fn env_at(time: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(time);
+    env
+}
+
+// the first fire costs 1000 lunc and every next one 1 more, half goes to the dividend and half is burned
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        dfc_address: "dfc".to_string(),
+        dflunc_address: "dflunc".to_string(),
+        dev_address: "dev".to_string(),
+        burned_address: "burned".to_string(),
+        max_delay_time: MAX_DELAY_TIME,
+        delay_time_per_burn: 100,
+        initial_lunc_amount_in: Uint128::from(1000u128),
+        fire_dfc_amount: Uint128::from(1u128),
+        price_step: PriceStep::Linear { increment: Uint128::from(1u128) },
+        dividend_percent: 50000,
+        burned_percent: 50000,
+        invite_percent: 0,
+        second_invite_percent: None,
+        dev_percent: 0,
+        unused_invite_destination: UnusedInviteDestination::Dividend,
+        prize_table: PrizeTable {
+            last_burner_percent: 0,
+            previous_burner_percents: vec![],
+            top_burner_percent: 0,
+        },
+        keeper_tip_percent: None,
+        jackpot_carry_percent: None,
+        timer_policy: None,
+        claim_window: None,
+        governance_address: None,
+    }
+}
+
+fn instantiate(deps: DepsMut, msg: InstantiateMsg) {
+    FomoDFCState::default().instantiate(deps, env_at(START_TIME), mock_info("owner", &[]), msg).unwrap();
+}
+
+fn execute(deps: DepsMut, time: u64, sender: &str, lunc: u128, msg: ExecuteMsg) -> Result<Response, ContractError> {
+    let funds = if lunc > 0 { coins(lunc, LUNC_DENOM) } else { vec![] };
+    FomoDFCState::default().execute(deps, env_at(time), mock_info(sender, &funds), msg)
+}
+
+// burns paying the exact price of the fires in the current round
+fn burn(deps: DepsMut, time: u64, sender: &str, fires: u64, team: Option<u64>) -> Response {
+    let state = FomoDFCState::default();
+    let cycle = state.current_cycle.may_load(deps.storage).unwrap().unwrap_or(0);
+    let price = state.lunc_amount_in_required.load(deps.storage, U64Key::from(cycle)).unwrap().u128();
+    let fires_price = (0..fires as u128).map(|fire| price + fire).sum();
+    let msg = ExecuteMsg::Burn { invite_address: None, fires, team };
+    execute(deps, time, sender, fires_price, msg).unwrap()
+}
+
+fn bank_sends(res: &Response, denom: &str) -> Vec<(String, Uint128)> {
+    res.messages
+        .iter()
+        .filter_map(|sub_msg| match &sub_msg.msg {
+            CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                amount.iter().find(|coin| coin.denom == denom).map(|coin| (to_address.clone(), coin.amount))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+// lunc sent by the response, send_lunc keeps 0.2% back for the chain tax
+fn lunc_sends(res: &Response) -> Vec<(String, Uint128)> {
+    bank_sends(res, LUNC_DENOM)
+}
+
+fn ustc_sends(res: &Response) -> Vec<(String, Uint128)> {
+    bank_sends(res, USTC_DENOM)
+}
+
+fn lunc_deposit(deps: Deps, user: &str) -> Uint128 {
+    let msg = QueryMsg::GetLuncDeposit { user_address: user.to_string() };
+    let res: GetLuncDepositResponse = from_binary(&FomoDFCState::default().query(deps, env_at(START_TIME), msg).unwrap()).unwrap();
+    res.amount
+}
+
+#[test]
+fn burn_overpayment_is_credited_to_the_lunc_deposit() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), instantiate_msg());
+
+    let msg = ExecuteMsg::Burn { invite_address: None, fires: 1, team: None };
+    let res = execute(deps.as_mut(), START_TIME, "alice", 1500, msg).unwrap();
+    // the burn share is the only lunc the burn sends
+    assert_eq!(lunc_sends(&res), vec![("burned".to_string(), Uint128::from(499u128))]);
+    assert_eq!(lunc_deposit(deps.as_ref(), "alice"), Uint128::from(500u128));
+
+    let res = execute(deps.as_mut(), START_TIME, "alice", 0, ExecuteMsg::WithdrawLuncDeposit {}).unwrap();
+    assert_eq!(lunc_sends(&res), vec![("alice".to_string(), Uint128::from(499u128))]);
+    assert_eq!(lunc_deposit(deps.as_ref(), "alice"), Uint128::zero());
+}
+
+#[test]
+fn lunc_pot_is_credited_to_the_last_burner() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), instantiate_msg());
+    let team = Team {
+        name: "pot".to_string(),
+        dividend_percent: 0,
+        burned_percent: 0,
+        pot_percent: 100000,
+        ustc_share_percent: 0,
+    };
+    execute(deps.as_mut(), START_TIME, "owner", 0, ExecuteMsg::SetTeam { team_id: 1, team }).unwrap();
+
+    burn(deps.as_mut(), START_TIME, "alice", 1, Some(1));
+    burn(deps.as_mut(), START_TIME + 10, "bob", 2, Some(1));
+    let state = FomoDFCState::default();
+    assert_eq!(state.cycle_lunc_pot.load(&deps.storage, U64Key::from(0)).unwrap(), Uint128::from(3003u128));
+
+    let end_time = state.end_time.load(&deps.storage).unwrap();
+    let res = execute(deps.as_mut(), end_time + 1, "keeper", 0, ExecuteMsg::SettleRound {}).unwrap();
+    assert!(lunc_sends(&res).is_empty());
+    assert_eq!(lunc_deposit(deps.as_ref(), "bob"), Uint128::from(3003u128));
+    assert_eq!(lunc_deposit(deps.as_ref(), "alice"), Uint128::zero());
+
+    // the winner opening the next round still sends lunc once, its burn share
+    let res = burn(deps.as_mut(), end_time + 2, "bob", 1, None);
+    assert_eq!(lunc_sends(&res), vec![("burned".to_string(), Uint128::from(499u128))]);
+    assert_eq!(lunc_deposit(deps.as_ref(), "bob"), Uint128::from(3003u128));
+}
+
+#[test]
+fn migrate_requires_an_owner_only_when_none_is_stored() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), instantiate_msg());
+    let state = FomoDFCState::default();
+    state.migrate(deps.as_mut(), env_at(START_TIME), MigrateMsg { owner: None }).unwrap();
+    state.migrate(deps.as_mut(), env_at(START_TIME), MigrateMsg { owner: Some("other".to_string()) }).unwrap();
+    assert_eq!(state.owner.load(&deps.storage).unwrap(), "owner");
+
+    // contracts deployed before ownership was stored
+    state.owner.remove(&mut deps.storage);
+    let err = state.migrate(deps.as_mut(), env_at(START_TIME), MigrateMsg { owner: None }).unwrap_err();
+    assert_eq!(err, StdError::generic_err("An owner is required for a contract without owner"));
+    state.migrate(deps.as_mut(), env_at(START_TIME), MigrateMsg { owner: Some("new_owner".to_string()) }).unwrap();
+    assert_eq!(state.owner.load(&deps.storage).unwrap(), "new_owner");
+}
+
+fn top_players(deps: Deps, start_after: Option<&str>) -> Vec<TopPlayer> {
+    let msg = QueryMsg::GetTopPlayers {
+        denom: Some(DividendDenom::Lunc),
+        start_after: start_after.map(String::from),
+        limit: Some(1),
+    };
+    let res: GetTopPlayersResponse = from_binary(&FomoDFCState::default().query(deps, env_at(START_TIME), msg).unwrap()).unwrap();
+    res.players
+}
+
+#[test]
+fn top_players_are_ranked_by_claimed_dividends() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), instantiate_msg());
+    burn(deps.as_mut(), START_TIME, "alice", 1, None);
+    burn(deps.as_mut(), START_TIME + 1, "bob", 1, None);
+    burn(deps.as_mut(), START_TIME + 2, "carol", 1, None);
+    for user in ["bob", "alice", "carol"] {
+        execute(deps.as_mut(), START_TIME + 3, user, 0, ExecuteMsg::ClaimLuncDividend { cycle: 0 }).unwrap();
+    }
+
+    let first = top_players(deps.as_ref(), None);
+    assert_eq!(first, vec![TopPlayer {
+        address: "alice".to_string(),
+        lunc_dividends_claimed: Uint128::from(1250u128),
+        ustc_dividends_claimed: Uint128::zero(),
+    }]);
+    let second = top_players(deps.as_ref(), Some("alice"));
+    assert_eq!(second, vec![TopPlayer {
+        address: "bob".to_string(),
+        lunc_dividends_claimed: Uint128::from(250u128),
+        ustc_dividends_claimed: Uint128::zero(),
+    }]);
+    // players who never claimed anything aren't ranked
+    assert!(top_players(deps.as_ref(), Some("bob")).is_empty());
+}
+
+// alice then bob burn a fire, so alice earns a lunc dividend, then the round is settled
+fn play_round(mut deps: DepsMut, time: u64) -> u64 {
+    burn(deps.branch(), time, "alice", 1, None);
+    burn(deps.branch(), time + 1, "bob", 1, None);
+    let end_time = FomoDFCState::default().end_time.load(deps.storage).unwrap();
+    execute(deps, end_time + 1, "keeper", 0, ExecuteMsg::SettleRound {}).unwrap();
+    end_time + 2
+}
+
+fn claimable(deps: Deps, user: &str, start_after: Option<u64>, limit: u32) -> GetClaimableResponse {
+    let msg = QueryMsg::GetClaimable { user_address: user.to_string(), start_after, limit: Some(limit) };
+    from_binary(&FomoDFCState::default().query(deps, env_at(START_TIME), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn claimable_pages_count_scanned_cycles() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), instantiate_msg());
+    let mut time = START_TIME;
+    for _ in 0..3 {
+        time = play_round(deps.as_mut(), time);
+    }
+    execute(deps.as_mut(), time, "alice", 0, ExecuteMsg::ClaimLuncDividend { cycle: 1 }).unwrap();
+
+    // the claimed cycle 1 uses up the page
+    let page = claimable(deps.as_ref(), "alice", None, 2);
+    assert_eq!(page.cycles, vec![ClaimableCycle { cycle: 0, lunc_amount: Uint128::from(1000u128), ustc_amount: Uint128::zero() }]);
+    assert_eq!(page.next_start_after, Some(1));
+
+    let page = claimable(deps.as_ref(), "alice", page.next_start_after, 2);
+    assert_eq!(page.cycles, vec![ClaimableCycle { cycle: 2, lunc_amount: Uint128::from(1000u128), ustc_amount: Uint128::zero() }]);
+    assert_eq!(page.total_lunc_amount, Uint128::from(1000u128));
+    assert_eq!(page.next_start_after, None);
+}
+
+#[test]
+fn claim_all_dividends_resumes_from_the_cursor() {
+    let mut deps = mock_dependencies();
+    deps.querier.with_reserved_ustc(1000);
+    instantiate(deps.as_mut(), instantiate_msg());
+    let mut time = START_TIME;
+    for _ in 0..3 {
+        time = play_round(deps.as_mut(), time);
+    }
+
+    // one send per denom for the lunc and ustc dividends of cycles 0 and 1
+    let msg = ExecuteMsg::ClaimAllDividends { cycles: None, limit: Some(2) };
+    let res = execute(deps.as_mut(), time, "alice", 0, msg.clone()).unwrap();
+    assert_eq!(lunc_sends(&res), vec![("alice".to_string(), Uint128::from(1996u128))]);
+    assert_eq!(ustc_sends(&res), vec![("alice".to_string(), Uint128::from(1000u128))]);
+    assert_eq!(FomoDFCState::default().user_claim_cursor.load(&deps.storage, Addr::unchecked("alice")).unwrap(), 1);
+
+    let res = execute(deps.as_mut(), time, "alice", 0, msg.clone()).unwrap();
+    assert_eq!(lunc_sends(&res), vec![("alice".to_string(), Uint128::from(998u128))]);
+    assert_eq!(ustc_sends(&res), vec![("alice".to_string(), Uint128::from(500u128))]);
+
+    let res = execute(deps.as_mut(), time, "alice", 0, msg).unwrap();
+    assert!(res.messages.is_empty());
+    assert!(claimable(deps.as_ref(), "alice", None, 10).cycles.is_empty());
+
+    // cycles given explicitly are claimed once
+    let msg = ExecuteMsg::ClaimAllDividends { cycles: Some(vec![2, 0, 2]), limit: None };
+    let res = execute(deps.as_mut(), time, "bob", 0, msg.clone()).unwrap();
+    assert_eq!(ustc_sends(&res), vec![("bob".to_string(), Uint128::from(1000u128))]);
+    let res = execute(deps.as_mut(), time, "bob", 0, msg).unwrap();
+    assert!(res.messages.is_empty());
+}
+
+#[test]
+fn anyone_can_settle_an_ended_round() {
+    let mut deps = mock_dependencies();
+    deps.querier.with_reserved_ustc(10000);
+    let mut msg = instantiate_msg();
+    msg.keeper_tip_percent = Some(1000);
+    msg.prize_table.last_burner_percent = 50000;
+    instantiate(deps.as_mut(), msg);
+
+    let err = execute(deps.as_mut(), START_TIME, "keeper", 0, ExecuteMsg::SettleRound {}).unwrap_err();
+    assert_eq!(err, ContractError::NoFireInCycle { cycle: 0 });
+    burn(deps.as_mut(), START_TIME, "alice", 1, None);
+    burn(deps.as_mut(), START_TIME + 1, "bob", 1, None);
+    let state = FomoDFCState::default();
+    let end_time = state.end_time.load(&deps.storage).unwrap();
+    let err = execute(deps.as_mut(), end_time, "keeper", 0, ExecuteMsg::SettleRound {}).unwrap_err();
+    assert_eq!(err, ContractError::RoundNotEnded { end_time });
+
+    // the keeper gets 1% of the claimed ustc, the last burner half of the rest and the fires share the remainder
+    let res = execute(deps.as_mut(), end_time + 1, "keeper", 0, ExecuteMsg::SettleRound {}).unwrap();
+    assert!(matches!(&res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) if contract_addr == "dflunc"));
+    assert_eq!(ustc_sends(&res), vec![
+        ("keeper".to_string(), Uint128::from(100u128)),
+        ("bob".to_string(), Uint128::from(4950u128)),
+    ]);
+    assert_eq!(state.cycle_avg_ustc_dividend.load(&deps.storage, U64Key::from(0)).unwrap(), Uint128::from(2475u128));
+    assert_eq!(state.current_cycle.load(&deps.storage).unwrap(), 1);
+
+    // the next round opens without fires
+    let err = execute(deps.as_mut(), end_time + 2, "keeper", 0, ExecuteMsg::SettleRound {}).unwrap_err();
+    assert_eq!(err, ContractError::NoFireInCycle { cycle: 1 });
+}
+
+fn claimable_lunc(deps: Deps, user: &str, cycle: u64) -> Uint128 {
+    claimable(deps, user, None, 10)
+        .cycles
+        .into_iter()
+        .find(|claimable| claimable.cycle == cycle)
+        .map(|claimable| claimable.lunc_amount)
+        .unwrap_or_default()
+}
+
+#[test]
+fn burn_buys_several_fires_at_the_stepped_price() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), instantiate_msg());
+    burn(deps.as_mut(), START_TIME, "alice", 1, None);
+
+    // 1001 + 1002 + 1003 lunc for three fires
+    let msg = ExecuteMsg::Burn { invite_address: None, fires: 3, team: None };
+    let res = execute(deps.as_mut(), START_TIME + 500, "bob", 3006, msg).unwrap();
+    assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "dfc".to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+            owner: "bob".to_string(),
+            recipient: "burned".to_string(),
+            amount: Uint128::from(3u128),
+        }).unwrap(),
+        funds: vec![],
+    }));
+
+    let state = FomoDFCState::default();
+    assert_eq!(state.cycle_total_fires.load(&deps.storage, U64Key::from(0)).unwrap(), 4);
+    assert_eq!(state.lunc_amount_in_required.load(&deps.storage, U64Key::from(0)).unwrap(), Uint128::from(1004u128));
+    // every fire extends the timer by 100 seconds
+    assert_eq!(state.end_time.load(&deps.storage).unwrap(), START_TIME + 1300);
+
+    // each fire's dividend also goes to the fires bob bought before it in the same burn
+    assert_eq!(claimable_lunc(deps.as_ref(), "alice", 0), Uint128::from(1417u128));
+    assert_eq!(claimable_lunc(deps.as_ref(), "bob", 0), Uint128::from(584u128));
+
+    let msg = ExecuteMsg::Burn { invite_address: None, fires: 101, team: None };
+    let err = execute(deps.as_mut(), START_TIME + 501, "bob", 1_000_000, msg).unwrap_err();
+    assert_eq!(err, ContractError::NotValidFires { max: 100 });
+    // storage writes of a failed execute aren't rolled back in unit tests, so it goes last
+    let msg = ExecuteMsg::Burn { invite_address: None, fires: 2, team: None };
+    let err = execute(deps.as_mut(), START_TIME + 501, "bob", 2008, msg).unwrap_err();
+    assert_eq!(err, ContractError::NotMatchedFund {
+        symbol: LUNC_DENOM.to_string(),
+        amount: Uint128::from(2008u128),
+        required: Uint128::from(2009u128),
+    });
+}
+
+fn dividend_carry(deps: Deps) -> GetDividendCarryResponse {
+    from_binary(&FomoDFCState::default().query(deps, env_at(START_TIME), QueryMsg::GetDividendCarry {}).unwrap()).unwrap()
+}
+
+#[test]
+fn dividend_dust_is_carried_forward() {
+    let mut deps = mock_dependencies();
+    deps.querier.with_reserved_ustc(1001);
+    instantiate(deps.as_mut(), instantiate_msg());
+    let state = FomoDFCState::default();
+
+    // 501 lunc over two fires leaves 1 for the next fire, which shares 502 over three
+    burn(deps.as_mut(), START_TIME, "alice", 1, None);
+    burn(deps.as_mut(), START_TIME + 1, "bob", 1, None);
+    burn(deps.as_mut(), START_TIME + 2, "carol", 1, None);
+    assert_eq!(state.cycle_avg_lunc_dividend.load(&deps.storage, U64Key::from(0)).unwrap(), Uint128::from(1250u128));
+    assert_eq!(dividend_carry(deps.as_ref()).lunc_amount, Uint128::from(1u128));
+    burn(deps.as_mut(), START_TIME + 3, "dave", 1, None);
+    assert_eq!(state.cycle_avg_lunc_dividend.load(&deps.storage, U64Key::from(0)).unwrap(), Uint128::from(1417u128));
+    assert_eq!(dividend_carry(deps.as_ref()).lunc_amount, Uint128::from(1u128));
+
+    // 1001 ustc over four fires leaves 1 for the next round
+    let end_time = state.end_time.load(&deps.storage).unwrap();
+    execute(deps.as_mut(), end_time + 1, "keeper", 0, ExecuteMsg::SettleRound {}).unwrap();
+    assert_eq!(state.cycle_avg_ustc_dividend.load(&deps.storage, U64Key::from(0)).unwrap(), Uint128::from(250u128));
+    assert_eq!(dividend_carry(deps.as_ref()).ustc_amount, Uint128::from(1u128));
+
+    // 1001 ustc with the carried 1 over two fires
+    play_round(deps.as_mut(), end_time + 2);
+    assert_eq!(state.cycle_avg_ustc_dividend.load(&deps.storage, U64Key::from(1)).unwrap(), Uint128::from(501u128));
+    assert_eq!(dividend_carry(deps.as_ref()).ustc_amount, Uint128::zero());
+}
+
+fn jackpot(deps: Deps) -> GetJackpotResponse {
+    from_binary(&FomoDFCState::default().query(deps, env_at(START_TIME), QueryMsg::GetJackpot {}).unwrap()).unwrap()
+}
+
+#[test]
+fn part_of_the_pot_rolls_into_the_next_round() {
+    let mut deps = mock_dependencies();
+    deps.querier.with_reserved_ustc(10000);
+    let mut msg = instantiate_msg();
+    msg.jackpot_carry_percent = Some(20000);
+    instantiate(deps.as_mut(), msg);
+    let state = FomoDFCState::default();
+
+    let time = play_round(deps.as_mut(), START_TIME);
+    assert_eq!(jackpot(deps.as_ref()), GetJackpotResponse {
+        ustc_jackpot_carry: Uint128::from(2000u128),
+        jackpot_carry_percent: 20000,
+    });
+    assert_eq!(state.cycle_avg_ustc_dividend.load(&deps.storage, U64Key::from(0)).unwrap(), Uint128::from(4000u128));
+
+    // the opening fire's dividend seeds the next fire instead of being burned
+    let res = burn(deps.as_mut(), time, "alice", 1, None);
+    assert_eq!(lunc_sends(&res), vec![("burned".to_string(), Uint128::from(499u128))]);
+    assert_eq!(dividend_carry(deps.as_ref()).lunc_amount, Uint128::from(500u128));
+    burn(deps.as_mut(), time + 1, "bob", 1, None);
+    assert_eq!(claimable_lunc(deps.as_ref(), "alice", 1), Uint128::from(1000u128));
+
+    // the claimed 10000 with the carried 2000 makes the pot of the next settlement
+    let end_time = state.end_time.load(&deps.storage).unwrap();
+    execute(deps.as_mut(), end_time + 1, "keeper", 0, ExecuteMsg::SettleRound {}).unwrap();
+    assert_eq!(jackpot(deps.as_ref()).ustc_jackpot_carry, Uint128::from(2400u128));
+    assert_eq!(state.cycle_avg_ustc_dividend.load(&deps.storage, U64Key::from(1)).unwrap(), Uint128::from(4800u128));
+}
+
+#[test]
+fn expired_dividends_are_swept_into_the_current_round() {
+    let mut deps = mock_dependencies();
+    deps.querier.with_reserved_ustc(1000);
+    let mut msg = instantiate_msg();
+    msg.claim_window = Some(1);
+    instantiate(deps.as_mut(), msg);
+    let state = FomoDFCState::default();
+    let mut time = START_TIME;
+    for _ in 0..3 {
+        time = play_round(deps.as_mut(), time);
+    }
+
+    // cycle 0 could be claimed until cycle 1 was settled
+    let expired = ContractError::DividendExpired { cycle: 0, claim_window: 1 };
+    let err = execute(deps.as_mut(), time, "alice", 0, ExecuteMsg::ClaimLuncDividend { cycle: 0 }).unwrap_err();
+    assert_eq!(err, expired);
+    let msg = ExecuteMsg::ClaimAllDividends { cycles: Some(vec![0, 1]), limit: None };
+    assert_eq!(execute(deps.as_mut(), time, "alice", 0, msg).unwrap_err(), expired);
+
+    // scanning passes over the expired cycles 0 and 1
+    let msg = ExecuteMsg::ClaimAllDividends { cycles: None, limit: None };
+    let res = execute(deps.as_mut(), time, "alice", 0, msg).unwrap();
+    assert_eq!(lunc_sends(&res), vec![("alice".to_string(), Uint128::from(998u128))]);
+    assert_eq!(ustc_sends(&res), vec![("alice".to_string(), Uint128::from(500u128))]);
+
+    let err = execute(deps.as_mut(), time, "anyone", 0, ExecuteMsg::SweepExpired { cycle: 2 }).unwrap_err();
+    assert_eq!(err, ContractError::NotSweepable { cycle: 2 });
+    execute(deps.as_mut(), time, "anyone", 0, ExecuteMsg::SweepExpired { cycle: 0 }).unwrap();
+    assert_eq!(state.cycle_lunc_pot.load(&deps.storage, U64Key::from(3)).unwrap(), Uint128::from(1000u128));
+    assert_eq!(jackpot(deps.as_ref()).ustc_jackpot_carry, Uint128::from(1000u128));
+    let err = execute(deps.as_mut(), time, "anyone", 0, ExecuteMsg::SweepExpired { cycle: 0 }).unwrap_err();
+    assert_eq!(err, ContractError::NoRewards {});
+}
+
+#[test]
+fn cleared_claim_window_keeps_later_rounds_claimable() {
+    let mut deps = mock_dependencies();
+    deps.querier.with_reserved_ustc(1000);
+    let mut msg = instantiate_msg();
+    msg.claim_window = Some(1);
+    instantiate(deps.as_mut(), msg);
+    let mut time = play_round(deps.as_mut(), START_TIME);
+
+    let msg = UpdateConfigMsg { clear_claim_window: Some(true), ..UpdateConfigMsg::default() };
+    execute(deps.as_mut(), time, "owner", 0, ExecuteMsg::UpdateConfig(Box::new(msg))).unwrap();
+    for _ in 0..3 {
+        time = play_round(deps.as_mut(), time);
+    }
+
+    // the round settled before keeps the window it was settled with
+    let err = execute(deps.as_mut(), time, "bob", 0, ExecuteMsg::ClaimUstcDividend { cycle: 0 }).unwrap_err();
+    assert_eq!(err, ContractError::DividendExpired { cycle: 0, claim_window: 1 });
+    let res = execute(deps.as_mut(), time, "bob", 0, ExecuteMsg::ClaimUstcDividend { cycle: 1 }).unwrap();
+    assert_eq!(ustc_sends(&res), vec![("bob".to_string(), Uint128::from(500u128))]);
+}